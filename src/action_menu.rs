@@ -0,0 +1,260 @@
+use crate::session::SessionItem;
+use crate::text_input::{InputEvent, TextInput};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use zellij_tile::prelude::KeyWithModifier;
+
+/// Actions that can be run on a list item from the action menu
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MenuAction {
+    /// Switch to the session, replacing the current view
+    Switch,
+    /// Resurrect a dead session and switch to it
+    Resurrect,
+    /// Replace a dead session with a new one of the same name, picking its folder and layout
+    ResurrectAs,
+    /// Attach to the session from a floating pane in the current session
+    AttachInFloatingPane,
+    /// Open a new tab in the current session at the item's directory
+    OpenTab,
+    /// Rename the current session
+    Rename,
+    /// Kill a running session
+    Kill,
+    /// Delete a resurrectable session
+    Delete,
+    /// Delete every resurrectable session older than a number of days
+    PurgeOld,
+    /// Create a session for the directory, choosing a layout
+    CreateWithLayout,
+    /// Create a session for the directory with the default layout
+    QuickCreate,
+    /// Keep the directory at the top of the list
+    Pin,
+    /// Stop keeping the directory at the top of the list
+    Unpin,
+    /// Use a chosen session name for the directory instead of the generated one
+    SetAlias,
+    /// Remove the directory from the zoxide database
+    RemoveFromZoxide,
+    /// Copy the directory path to the clipboard
+    CopyPath,
+    /// Copy the session name to the clipboard
+    CopySessionName,
+    /// Copy a `zellij attach` command for the session to the clipboard
+    CopyAttachCommand,
+}
+
+impl MenuAction {
+    /// Label shown in the action menu
+    pub fn label(&self) -> &'static str {
+        match self {
+            MenuAction::Switch => "Switch to session",
+            MenuAction::Resurrect => "Resurrect session",
+            MenuAction::ResurrectAs => "Resurrect as new session…",
+            MenuAction::AttachInFloatingPane => "Attach in floating pane",
+            MenuAction::OpenTab => "Open new tab in directory",
+            MenuAction::Rename => "Rename session",
+            MenuAction::Kill => "Kill session",
+            MenuAction::Delete => "Delete session",
+            MenuAction::PurgeOld => "Purge old resurrectable sessions",
+            MenuAction::CreateWithLayout => "Create session with layout",
+            MenuAction::QuickCreate => "Create session with default layout",
+            MenuAction::Pin => "Pin directory",
+            MenuAction::Unpin => "Unpin directory",
+            MenuAction::SetAlias => "Set session name alias…",
+            MenuAction::RemoveFromZoxide => "Remove from zoxide",
+            MenuAction::CopyPath => "Copy path",
+            MenuAction::CopySessionName => "Copy session name",
+            MenuAction::CopyAttachCommand => "Copy attach command",
+        }
+    }
+
+    /// Actions valid for an item
+    pub fn for_item(item: &SessionItem, is_pinned: bool) -> Vec<MenuAction> {
+        match item {
+            SessionItem::ExistingSession { is_current, .. } => {
+                let mut actions = if *is_current {
+                    vec![MenuAction::OpenTab, MenuAction::Rename, MenuAction::Kill]
+                } else {
+                    vec![
+                        MenuAction::Switch,
+                        MenuAction::AttachInFloatingPane,
+                        MenuAction::OpenTab,
+                        MenuAction::Kill,
+                    ]
+                };
+                actions.extend([
+                    MenuAction::CopyPath,
+                    MenuAction::CopySessionName,
+                    MenuAction::CopyAttachCommand,
+                ]);
+                actions
+            }
+            SessionItem::ResurrectableSession { .. } => vec![
+                MenuAction::Resurrect,
+                MenuAction::ResurrectAs,
+                MenuAction::Delete,
+                MenuAction::PurgeOld,
+                MenuAction::CopySessionName,
+                MenuAction::CopyAttachCommand,
+            ],
+            SessionItem::Directory { .. } => vec![
+                MenuAction::CreateWithLayout,
+                MenuAction::QuickCreate,
+                MenuAction::OpenTab,
+                if is_pinned {
+                    MenuAction::Unpin
+                } else {
+                    MenuAction::Pin
+                },
+                MenuAction::SetAlias,
+                MenuAction::RemoveFromZoxide,
+                MenuAction::CopyPath,
+            ],
+        }
+    }
+}
+
+/// Context menu listing every action valid for the selected item
+pub struct ActionMenu {
+    /// Item the actions apply to
+    item: SessionItem,
+    /// Actions offered for the item
+    actions: Vec<MenuAction>,
+    /// Current filter term
+    input: TextInput,
+    /// Actions matching the filter term, with matched label indices
+    results: Vec<(MenuAction, Vec<usize>)>,
+    /// Selected index into the results
+    selected_index: usize,
+    /// Fuzzy matcher instance
+    matcher: SkimMatcherV2,
+}
+
+impl ActionMenu {
+    /// Create a menu for an item
+    pub fn for_item(item: SessionItem, is_pinned: bool) -> Self {
+        let actions = MenuAction::for_item(&item, is_pinned);
+        let mut menu = Self {
+            item,
+            actions,
+            input: TextInput::default(),
+            results: Vec::new(),
+            selected_index: 0,
+            matcher: SkimMatcherV2::default().use_cache(true),
+        };
+        menu.update_results();
+        menu
+    }
+
+    /// Get the item the menu was opened on
+    pub fn item(&self) -> &SessionItem {
+        &self.item
+    }
+
+    /// Get the actions matching the filter, with matched label indices
+    pub fn results(&self) -> &[(MenuAction, Vec<usize>)] {
+        &self.results
+    }
+
+    /// Get the actions offered before filtering
+    pub fn actions(&self) -> &[MenuAction] {
+        &self.actions
+    }
+
+    /// Get current filter term
+    pub fn search_term(&self) -> &str {
+        self.input.text()
+    }
+
+    /// Get filter input (for UI rendering)
+    pub fn input(&self) -> &TextInput {
+        &self.input
+    }
+
+    /// Get selected action index
+    pub fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    /// Get selected action
+    pub fn selected_action(&self) -> Option<MenuAction> {
+        self.results
+            .get(self.selected_index)
+            .map(|(action, _)| *action)
+    }
+
+    /// Handle a line-editing key in the filter, re-filtering if the term changed
+    pub fn handle_input_key(&mut self, key: &KeyWithModifier) -> InputEvent {
+        let event = self.input.handle_key(key);
+        if event == InputEvent::Changed {
+            self.update_results();
+        }
+        event
+    }
+
+    /// Insert pasted text into the filter
+    pub fn paste(&mut self, text: &str) {
+        self.input.insert_str(text);
+        self.update_results();
+    }
+
+    /// Clear filter term
+    pub fn clear_search(&mut self) {
+        self.input.clear();
+        self.update_results();
+    }
+
+    /// Select an action of the filtered list by index
+    pub fn select(&mut self, index: usize) {
+        if index < self.results.len() {
+            self.selected_index = index;
+        }
+    }
+
+    /// Move selection up
+    pub fn move_selection_up(&mut self) {
+        if self.selected_index == 0 {
+            self.selected_index = self.results.len().saturating_sub(1);
+        } else {
+            self.selected_index -= 1;
+        }
+    }
+
+    /// Move selection down
+    pub fn move_selection_down(&mut self) {
+        if self.selected_index >= self.results.len().saturating_sub(1) {
+            self.selected_index = 0;
+        } else {
+            self.selected_index += 1;
+        }
+    }
+
+    /// Filter actions by the current term, keeping menu order when not filtering
+    fn update_results(&mut self) {
+        if self.input.is_empty() {
+            self.results = self
+                .actions
+                .iter()
+                .map(|action| (*action, Vec::new()))
+                .collect();
+        } else {
+            let mut matches: Vec<(i64, MenuAction, Vec<usize>)> = self
+                .actions
+                .iter()
+                .filter_map(|action| {
+                    self.matcher
+                        .fuzzy_indices(action.label(), self.input.text())
+                        .map(|(score, indices)| (score, *action, indices))
+                })
+                .collect();
+            matches.sort_by_key(|(score, _, _)| std::cmp::Reverse(*score));
+            self.results = matches
+                .into_iter()
+                .map(|(_, action, indices)| (action, indices))
+                .collect();
+        }
+        self.selected_index = 0;
+    }
+}
@@ -0,0 +1,28 @@
+use std::collections::BTreeMap;
+use zellij_tile::prelude::run_command;
+
+/// Context key marking a `RunCommandResult` as the outcome of a clipboard copy
+pub const CLIPBOARD_CONTEXT: &str = "clipboard_copy";
+
+/// Clipboard tools tried in order when no `copy_command` is configured
+const DEFAULT_COPY_COMMAND: &str = "if command -v pbcopy >/dev/null 2>&1; then pbcopy; \
+     elif [ -n \"$WAYLAND_DISPLAY\" ] && command -v wl-copy >/dev/null 2>&1; then wl-copy; \
+     elif command -v xclip >/dev/null 2>&1; then xclip -selection clipboard; \
+     elif command -v xsel >/dev/null 2>&1; then xsel --clipboard --input; \
+     else echo 'no clipboard tool found (set copy_command)' >&2; exit 1; fi";
+
+/// Copy text to the system clipboard on the host. The plugin API has no
+/// clipboard command, so the text is piped into a clipboard tool instead,
+/// the same way Zellij's own `copy_command` option works.
+///
+/// `description` names what was copied and comes back in the context of the
+/// `RunCommandResult` so the UI can confirm it.
+pub fn copy(text: &str, description: &str, copy_command: Option<&str>) {
+    let mut context = BTreeMap::new();
+    context.insert(CLIPBOARD_CONTEXT.to_string(), description.to_string());
+    let script = format!(
+        "printf '%s' \"$1\" | {{ {}; }}",
+        copy_command.unwrap_or(DEFAULT_COPY_COMMAND)
+    );
+    run_command(&["sh", "-c", &script, "sh", text], context);
+}
@@ -1,4 +1,10 @@
 use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::session::naming::NamingRules;
+use crate::session::GroupBy;
+use crate::ui::{RowFormat, Theme};
+use crate::zoxide::{SearchMode, SortPolicy};
 
 /// Plugin configuration loaded from Zellij layout
 #[derive(Debug, Clone)]
@@ -9,8 +15,36 @@ pub struct Config {
     pub session_separator: String,
     /// Whether you'd like resurrectable sessions to be shown in the session list
     pub show_resurrectable_sessions: bool,
+    /// Resurrectable sessions exited longer ago than this are left out of the session list
+    pub resurrectable_max_age: Option<Duration>,
     /// Base paths to strip from directory names when generating session names
     pub base_paths: Vec<String>,
+    /// How session names are generated, for all directories or per base path (default: smart)
+    pub naming_strategy: NamingRules,
+    /// Session names used for directories instead of generated ones, by path
+    pub session_aliases: BTreeMap<String, String>,
+    /// Longest session name in bytes, used when the socket directory can't be discovered
+    pub max_session_name_length: Option<usize>,
+    /// Shell command that receives text to copy on stdin (default: first available clipboard tool)
+    pub copy_command: Option<String>,
+    /// Matching algorithm used when searching (default: fuzzy)
+    pub search_mode: SearchMode,
+    /// How search results are ordered (default: sessions first)
+    pub sort_policy: SortPolicy,
+    /// Whether items previously chosen for a search term are ranked first when it's typed again
+    pub rank_by_history: bool,
+    /// How the main list groups directories and sessions (default: flat list)
+    pub group_by: GroupBy,
+    /// Whether a directory with running sessions is shown as a single row for its session
+    pub merge_directories: bool,
+    /// Column template for list rows (default: built-in single column format)
+    pub row_format: Option<RowFormat>,
+    /// Styles and icons used when rendering
+    pub theme: Theme,
+    /// Whether the main list starts in a vim-style normal mode, with `/` to search
+    pub vim_mode: bool,
+    /// Problems with configuration values that were skipped, reported once the plugin loads
+    pub warnings: Vec<String>,
 }
 
 impl Default for Config {
@@ -19,7 +53,21 @@ impl Default for Config {
             default_layout: None,
             session_separator: ".".to_string(),
             show_resurrectable_sessions: false,
+            resurrectable_max_age: None,
             base_paths: Vec::new(),
+            naming_strategy: NamingRules::default(),
+            session_aliases: BTreeMap::new(),
+            max_session_name_length: None,
+            copy_command: None,
+            search_mode: SearchMode::default(),
+            sort_policy: SortPolicy::default(),
+            rank_by_history: false,
+            group_by: GroupBy::default(),
+            merge_directories: false,
+            row_format: None,
+            theme: Theme::default(),
+            vim_mode: false,
+            warnings: Vec::new(),
         }
     }
 }
@@ -27,6 +75,21 @@ impl Default for Config {
 impl Config {
     /// Create configuration from Zellij plugin configuration
     pub fn from_zellij_config(config: &BTreeMap<String, String>) -> Self {
+        let (naming_strategy, invalid_naming_rules) = config
+            .get("naming_strategy")
+            .map(|rules| NamingRules::from_config(rules))
+            .unwrap_or_default();
+        let (theme, invalid_theme_keys) = Theme::from_config(config);
+        let mut warnings: Vec<String> = invalid_naming_rules
+            .into_iter()
+            .map(|entry| format!("Skipped invalid naming_strategy entry '{}'", entry))
+            .collect();
+        warnings.extend(
+            invalid_theme_keys
+                .into_iter()
+                .map(|key| format!("Skipped invalid {} '{}'", key, config[key])),
+        );
+
         Self {
             default_layout: config.get("default_layout").cloned(),
             session_separator: config
@@ -37,6 +100,9 @@ impl Config {
                 .get("show_resurrectable_sessions")
                 .map(|v| v == "true")
                 .unwrap_or(false),
+            resurrectable_max_age: config
+                .get("resurrectable_max_age")
+                .and_then(|age| parse_age(age)),
             base_paths: config
                 .get("base_paths")
                 .map(|paths| {
@@ -47,6 +113,85 @@ impl Config {
                         .collect()
                 })
                 .unwrap_or_else(Vec::new),
+            naming_strategy,
+            session_aliases: config
+                .get("session_aliases")
+                .map(|aliases| parse_aliases(aliases))
+                .unwrap_or_default(),
+            max_session_name_length: config
+                .get("max_session_name_length")
+                .and_then(|length| length.trim().parse().ok())
+                .filter(|length| *length > 0),
+            copy_command: config
+                .get("copy_command")
+                .map(|command| command.trim().to_string())
+                .filter(|command| !command.is_empty()),
+            search_mode: parse_option(
+                config,
+                "search_mode",
+                SearchMode::from_config,
+                &mut warnings,
+            )
+            .unwrap_or_default(),
+            sort_policy: parse_option(
+                config,
+                "sort_policy",
+                SortPolicy::from_config,
+                &mut warnings,
+            )
+            .unwrap_or_default(),
+            rank_by_history: config
+                .get("rank_by_history")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+            group_by: parse_option(config, "group_by", GroupBy::from_config, &mut warnings)
+                .unwrap_or_default(),
+            merge_directories: config
+                .get("merge_directories")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+            row_format: parse_option(config, "row_format", RowFormat::from_config, &mut warnings),
+            theme,
+            vim_mode: config.get("vim_mode").map(|v| v == "true").unwrap_or(false),
+            warnings,
         }
     }
 }
+
+/// Parse an option with `parse`, adding a warning when it's set to an invalid value
+fn parse_option<T>(
+    config: &BTreeMap<String, String>,
+    key: &str,
+    parse: impl Fn(&str) -> Option<T>,
+    warnings: &mut Vec<String>,
+) -> Option<T> {
+    let value = config.get(key)?;
+    let parsed = parse(value);
+    if parsed.is_none() {
+        warnings.push(format!("Skipped invalid {} '{}'", key, value));
+    }
+    parsed
+}
+
+/// Parse an age given as a number of days, e.g. `30`, or as a duration, e.g. `2weeks` or `36h`
+fn parse_age(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    match value.parse::<u64>() {
+        Ok(days) => days.checked_mul(SECONDS_PER_DAY).map(Duration::from_secs),
+        Err(_) => humantime::parse_duration(value).ok(),
+    }
+}
+
+/// Parse pipe-separated `path=name` session name aliases
+fn parse_aliases(value: &str) -> BTreeMap<String, String> {
+    value
+        .split('|')
+        .filter_map(|entry| entry.split_once('='))
+        .map(|(path, name)| (path.trim().trim_end_matches('/'), name.trim()))
+        .filter(|(path, name)| !path.is_empty() && !name.is_empty())
+        .map(|(path, name)| (path.to_string(), name.to_string()))
+        .collect()
+}
+
+/// Seconds in a day, the unit of ages given as plain numbers
+pub const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
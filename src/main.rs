@@ -1,12 +1,25 @@
+mod action_menu;
+mod clipboard;
 mod config;
+mod navigation;
 mod new_session_info;
+mod notifications;
 mod session;
+mod socket;
 mod state;
+mod store;
+mod text_input;
 mod ui;
+mod vim;
 mod zoxide;
 
+use notifications::Severity;
+use session::naming::NameContext;
+use session::validation;
+use session::GroupBy;
 use state::PluginState;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use ui::row_format::Field;
 use ui::PluginRenderer;
 use zellij_tile::prelude::*;
 
@@ -28,8 +41,11 @@ impl ZellijPlugin for PluginState {
             EventType::ModeUpdate,
             EventType::SessionUpdate,
             EventType::Key,
+            EventType::PastedText,
             EventType::RunCommandResult,
             EventType::PermissionRequestResult,
+            EventType::Timer,
+            EventType::Mouse,
         ]);
 
         // Don't fetch zoxide directories immediately - wait for permissions
@@ -40,18 +56,31 @@ impl ZellijPlugin for PluginState {
         let mut should_render = false;
 
         match event {
-            Event::ModeUpdate(mode_info) => {
-                self.set_colors(mode_info.style.colors.into());
+            Event::ModeUpdate(_) => {
+                // Styles are resolved by Zellij from its current palette, so just redraw
                 should_render = true;
             }
             Event::Key(key) => {
                 should_render = self.handle_key(key);
             }
+            Event::PastedText(text) => {
+                should_render = self.handle_paste(&text);
+            }
+            Event::Mouse(mouse) => {
+                should_render = self.handle_mouse(mouse);
+            }
+            Event::Timer(elapsed) => {
+                should_render = self.expire_notification(elapsed);
+            }
             Event::PermissionRequestResult(permission_status) => {
                 match permission_status {
                     PermissionStatus::Granted => {
                         // Now that we have permissions, fetch zoxide directories
                         self.fetch_zoxide_directories();
+                        socket::fetch_socket_dir();
+                        for file in store::StoreFile::ALL {
+                            store::load(file);
+                        }
                         should_render = true;
                     }
                     PermissionStatus::Denied => {
@@ -69,10 +98,16 @@ impl ZellijPlugin for PluginState {
                 should_render = true;
             }
             Event::RunCommandResult(exit_code, stdout, stderr, context) => {
-                if context.contains_key("zoxide_query") {
+                if let Some(query) = context.get("zoxide_query") {
                     if exit_code == Some(0) {
                         let stdout_str = String::from_utf8_lossy(&stdout);
-                        self.process_zoxide_output(&stdout_str);
+                        let count = self.process_zoxide_output(&stdout_str);
+                        if query == "reload" {
+                            self.notify(
+                                Severity::Info,
+                                format!("Reloaded zoxide ({} directories)", count),
+                            );
+                        }
                         should_render = true;
                     } else {
                         let stderr_str = String::from_utf8_lossy(&stderr);
@@ -82,6 +117,82 @@ impl ZellijPlugin for PluginState {
                         ));
                         should_render = true;
                     }
+                } else if context.contains_key("git_info") {
+                    let stdout_str = String::from_utf8_lossy(&stdout);
+                    let mut repo_roots = HashMap::new();
+                    let mut git_branches = HashMap::new();
+                    let mut git_remotes = HashMap::new();
+                    for line in stdout_str.lines() {
+                        let mut fields = line.split('\t');
+                        if let (Some(directory), Some(root), Some(branch)) =
+                            (fields.next(), fields.next(), fields.next())
+                        {
+                            repo_roots.insert(directory.to_string(), root.to_string());
+                            if !branch.is_empty() {
+                                git_branches.insert(directory.to_string(), branch.to_string());
+                            }
+                            if let Some(remote) = fields.next().filter(|r| !r.is_empty()) {
+                                git_remotes.insert(directory.to_string(), remote.to_string());
+                            }
+                        }
+                    }
+                    self.set_git_info(repo_roots, git_branches, git_remotes);
+                    if self.config().naming_strategy.uses_git() {
+                        self.regenerate_session_names();
+                    }
+                    should_render = true;
+                } else if let Some(path) = context.get("zoxide_remove") {
+                    if exit_code == Some(0) {
+                        self.fetch_zoxide_directories();
+                        self.notify(Severity::Info, format!("Removed {} from zoxide", path));
+                    } else {
+                        let stderr_str = String::from_utf8_lossy(&stderr);
+                        self.notify(
+                            Severity::Error,
+                            format!(
+                                "Failed to remove directory from zoxide: {}",
+                                stderr_str.trim()
+                            ),
+                        );
+                    }
+                    should_render = true;
+                } else if let Some(description) = context.get(clipboard::CLIPBOARD_CONTEXT) {
+                    if exit_code == Some(0) {
+                        self.notify(
+                            Severity::Info,
+                            format!("Copied {} to clipboard", description),
+                        );
+                    } else {
+                        let stderr_str = String::from_utf8_lossy(&stderr);
+                        self.notify(
+                            Severity::Error,
+                            format!("Failed to copy {}: {}", description, stderr_str.trim()),
+                        );
+                    }
+                    should_render = true;
+                } else if let Some(file) = context
+                    .get(store::STORE_LOAD_CONTEXT)
+                    .and_then(|name| store::StoreFile::from_file_name(name))
+                {
+                    let stdout_str = String::from_utf8_lossy(&stdout);
+                    self.load_store_file(file, &stdout_str);
+                    should_render = true;
+                } else if let Some(name) = context.get(store::STORE_SAVE_CONTEXT) {
+                    if exit_code != Some(0) {
+                        let stderr_str = String::from_utf8_lossy(&stderr);
+                        self.notify(
+                            Severity::Warning,
+                            format!("Failed to save {}: {}", name, stderr_str.trim()),
+                        );
+                        should_render = true;
+                    }
+                } else if let Some(zellij_version) = context.get(socket::SOCKET_DIR_CONTEXT) {
+                    let stdout_str = String::from_utf8_lossy(&stdout);
+                    if exit_code == Some(0) && self.set_socket_dir(&stdout_str, zellij_version) {
+                        // Names generated before the budget was known may be too long or cut short
+                        self.regenerate_session_names();
+                        should_render = true;
+                    }
                 }
             }
             _ => (),
@@ -139,7 +250,9 @@ impl ZellijPlugin for PluginState {
     }
 
     fn render(&mut self, rows: usize, cols: usize) {
-        PluginRenderer::render(self, rows, cols);
+        self.set_viewport(rows, cols);
+        let regions = PluginRenderer::render(self, rows, cols);
+        self.set_hit_regions(regions);
     }
 }
 
@@ -150,7 +263,31 @@ impl PluginState {
         run_command(&["zoxide", "query", "-l", "-s"], context);
     }
 
-    fn process_zoxide_output(&mut self, output: &str) {
+    /// Fetch zoxide directories again, reporting how many were loaded
+    fn reload_zoxide_directories(&mut self) {
+        let mut context = BTreeMap::new();
+        context.insert("zoxide_query".to_string(), "reload".to_string());
+        run_command(&["zoxide", "query", "-l", "-s"], context);
+    }
+
+    fn fetch_git_info(&self, directories: &[zoxide::ZoxideDirectory]) {
+        let mut context = BTreeMap::new();
+        context.insert("git_info".to_string(), "true".to_string());
+        // Print "directory<TAB>repo root<TAB>branch<TAB>origin remote" for every directory
+        // inside a git repository
+        let script = "for dir in \"$@\"; do \
+            root=$(git -C \"$dir\" rev-parse --show-toplevel 2>/dev/null) \
+            && printf '%s\\t%s\\t%s\\t%s\\n' \"$dir\" \"$root\" \
+            \"$(git -C \"$dir\" branch --show-current 2>/dev/null)\" \
+            \"$(git -C \"$dir\" remote get-url origin 2>/dev/null)\"; \
+            done; true";
+        let mut command = vec!["sh", "-c", script, "sh"];
+        command.extend(directories.iter().map(|dir| dir.directory.as_str()));
+        run_command(&command, context);
+    }
+
+    /// Load directories from `zoxide query` output, returning how many were loaded
+    fn process_zoxide_output(&mut self, output: &str) -> usize {
         let mut directories = Vec::new();
 
         for line in output.lines() {
@@ -183,16 +320,49 @@ impl PluginState {
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
 
+        let shows_branch = self
+            .config()
+            .row_format
+            .as_ref()
+            .is_some_and(|format| format.uses(Field::Branch));
+        if self.config().group_by == GroupBy::Repo
+            || shows_branch
+            || self.config().naming_strategy.uses_git()
+        {
+            self.fetch_git_info(&directories);
+        }
+        let count = directories.len();
+        self.update_zoxide_directories(directories);
+        count
+    }
+
+    /// Generate the session names of the loaded zoxide directories again, after something
+    /// they depend on changed
+    fn regenerate_session_names(&mut self) {
+        let mut directories = self.zoxide_directories().to_vec();
+        self.generate_smart_session_names(&mut directories);
         self.update_zoxide_directories(directories);
     }
 
     fn generate_smart_session_names(&self, directories: &mut Vec<zoxide::ZoxideDirectory>) {
         use std::collections::HashMap;
 
+        // Aliases come first, their names are reserved for their directories
+        let mut aliased = vec![false; directories.len()];
+        for (i, dir) in directories.iter_mut().enumerate() {
+            if let Some(alias) = self.session_alias(&dir.directory) {
+                dir.session_name = alias.to_string();
+                aliased[i] = true;
+            }
+        }
+
         // First pass: collect all basenames and find conflicts
         let mut basename_groups: HashMap<String, Vec<usize>> = HashMap::new();
 
         for (i, dir) in directories.iter().enumerate() {
+            if aliased[i] {
+                continue;
+            }
             let basename = std::path::Path::new(&dir.directory)
                 .file_name()
                 .unwrap_or_default()
@@ -214,6 +384,9 @@ impl PluginState {
                     // Add context for nested directories
                     let smart_name = self.generate_context_aware_name(path, directories, &indices);
                     directories[i].session_name = smart_name;
+                } else if basename.len() > self.session_name_budget() {
+                    // No conflict and not nested, but the basename doesn't fit
+                    directories[i].session_name = self.apply_smart_truncation(&[&basename], 1);
                 } else {
                     // No conflict and not nested, use basename
                     directories[i].session_name = basename;
@@ -227,6 +400,118 @@ impl PluginState {
                 }
             }
         }
+
+        self.apply_naming_strategies(directories, &aliased);
+        self.avoid_alias_names(directories, &aliased);
+    }
+
+    /// Rename directories whose generated name is the alias of another directory, adding
+    /// parent folders until the name is free, or else an incremented suffix
+    fn avoid_alias_names(&self, directories: &mut [zoxide::ZoxideDirectory], aliased: &[bool]) {
+        let reserved: HashSet<String> = directories
+            .iter()
+            .zip(aliased)
+            .filter(|(_, is_alias)| **is_alias)
+            .map(|(dir, _)| dir.session_name.clone())
+            .collect();
+        if reserved.is_empty() {
+            return;
+        }
+
+        let separator = &self.config().session_separator;
+        for (dir, _) in directories
+            .iter_mut()
+            .zip(aliased)
+            .filter(|(_, is_alias)| !**is_alias)
+        {
+            if !reserved.contains(&dir.session_name) {
+                continue;
+            }
+            let normalized_path = self.normalize_path(&dir.directory);
+            let segments: Vec<&str> = normalized_path
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .collect();
+            let with_context = (2..=segments.len())
+                .map(|context_length| {
+                    let candidate = segments[segments.len() - context_length..].join(separator);
+                    if candidate.len() > self.session_name_budget() {
+                        self.apply_smart_truncation(&segments, context_length)
+                    } else {
+                        candidate
+                    }
+                })
+                .find(|candidate| !reserved.contains(candidate));
+            dir.session_name = with_context.unwrap_or_else(|| {
+                (2..)
+                    .map(|n| format!("{}{}{}", dir.session_name, separator, n))
+                    .find(|candidate| !reserved.contains(candidate))
+                    .unwrap_or_default()
+            });
+        }
+    }
+
+    /// Rename directories whose `naming_strategy` isn't the smart default. Directories the
+    /// strategy can't name, or that would share a name with another directory, keep their
+    /// smart name. Aliased directories keep their alias.
+    fn apply_naming_strategies(
+        &self,
+        directories: &mut [zoxide::ZoxideDirectory],
+        aliased: &[bool],
+    ) {
+        let separator = &self.config().session_separator;
+        let strategy_names: Vec<Option<String>> = directories
+            .iter()
+            .zip(aliased)
+            .map(|(dir, is_alias)| {
+                if *is_alias {
+                    return None;
+                }
+                let path = dir.directory.as_str();
+                let (base_path, strategy) = self.config().naming_strategy.strategy_for(path);
+                let relative_path = match base_path {
+                    Some(base_path) => path
+                        .strip_prefix(base_path)
+                        .filter(|rest| !rest.is_empty())
+                        .unwrap_or(path)
+                        .to_string(),
+                    None => self.normalize_path(path),
+                };
+                let context = NameContext {
+                    relative_path: &relative_path,
+                    repo_root: self.repo_root(path),
+                    remote_url: self.git_remote(path),
+                    branch: self.git_branch(path),
+                };
+                let mut name = strategy.name(&context, separator)?;
+                if name.len() > self.session_name_budget() {
+                    let segments: Vec<&str> = name.split(separator.as_str()).collect();
+                    name = self.apply_smart_truncation(&segments, segments.len());
+                }
+                validation::validate_name(&name, self.session_name_budget()).ok()?;
+                Some(name)
+            })
+            .collect();
+
+        let final_names: Vec<&str> = directories
+            .iter()
+            .zip(&strategy_names)
+            .map(|(dir, name)| name.as_deref().unwrap_or(&dir.session_name))
+            .collect();
+        let mut name_counts: HashMap<&str, usize> = HashMap::new();
+        for name in &final_names {
+            *name_counts.entry(name).or_default() += 1;
+        }
+        let unique: Vec<bool> = final_names
+            .iter()
+            .map(|name| name_counts[name] == 1)
+            .collect();
+
+        for ((dir, name), unique) in directories.iter_mut().zip(strategy_names).zip(unique) {
+            if let Some(name) = name.filter(|_| unique) {
+                dir.session_name = name;
+            }
+        }
     }
 
     fn is_nested_in_zoxide_directories(
@@ -333,9 +618,8 @@ impl PluginState {
         // Zellij session names depend on the location of the socket
         // Currently, the length limit is 108 bytes for Unix domain sockets
         // The name of the session + the socket path must fit within this limit
-        // Since we run in a WASM runtime, there's no way to programmatically get the socket path
-        // therefore, we enforce a name length of ~29 characters to stay safe
-        if session_name.len() > 29 {
+        // The socket directory is discovered on the host, see `session_name_budget`
+        if session_name.len() > self.session_name_budget() {
             session_name = self.apply_smart_truncation(&segments, required_segments);
         }
 
@@ -392,8 +676,7 @@ impl PluginState {
 
     fn apply_smart_truncation(&self, segments: &[&str], min_segments: usize) -> String {
         let separator = &self.config().session_separator;
-        let max_length = 29;
-        eprintln!("Applying smart truncation for segments: {:?} with min_segments: {}", segments, min_segments);
+        let max_length = self.session_name_budget();
 
         // Start with minimum required segments from the right
         let mut result_segments: Vec<String> = segments
@@ -422,7 +705,7 @@ impl PluginState {
             if current_length > max_length && result_segments.len() == 1 {
                 let sep_len = if result_segments.len() > 1 { separator.len() } else { 0 };
                 let available = max_length.saturating_sub(sep_len);
-                result_segments[0].truncate(available);
+                truncate_on_char_boundary(&mut result_segments[0], available);
                 current_length = result_segments.join(separator).len();
             }
         }
@@ -437,7 +720,6 @@ impl PluginState {
             let mut test_segments = vec![abbreviated.clone()];
             test_segments.extend(result_segments.clone());
             let test_length = test_segments.join(separator).len();
-            eprintln!("test_length with segment '{}': {}", segment, test_length);
 
             if test_length <= max_length {
                 result_segments.insert(0, abbreviated);
@@ -451,7 +733,7 @@ impl PluginState {
         // Final safety check - ensure we're at or under max_length
         let mut final_result = result_segments.join(separator);
         if final_result.len() > max_length {
-            final_result.truncate(max_length);
+            truncate_on_char_boundary(&mut final_result, max_length);
         }
 
         final_result
@@ -500,3 +782,34 @@ impl PluginState {
         segment.to_string()
     }
 }
+
+/// Cut a name down to at most `max_length` bytes without splitting a character
+fn truncate_on_char_boundary(name: &mut String, max_length: usize) {
+    let end = name
+        .char_indices()
+        .map(|(i, c)| i + c.len_utf8())
+        .take_while(|end| *end <= max_length)
+        .last()
+        .unwrap_or(0);
+    name.truncate(end);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncation_keeps_whole_characters() {
+        let mut name = "プロジェクト".to_string();
+        truncate_on_char_boundary(&mut name, 10);
+        assert_eq!(name, "プロジ");
+    }
+
+    #[test]
+    fn smart_truncation_of_non_ascii_names_fits_budget() {
+        let state = PluginState::default();
+        let name = state.apply_smart_truncation(&["あ-い-う-え-お-か-き-く"], 1);
+        assert!(name.len() <= state.session_name_budget(), "{}", name);
+        assert!(name.starts_with("あ-い"), "{}", name);
+    }
+}
@@ -0,0 +1,70 @@
+use zellij_tile::prelude::{BareKey, KeyModifier, KeyWithModifier};
+
+/// A move of the selection across a list, beyond the single steps of the arrow keys
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Jump {
+    PageUp,
+    PageDown,
+    HalfPageUp,
+    HalfPageDown,
+    First,
+    Last,
+}
+
+impl Jump {
+    /// The jump bound to a key. Home/End and Ctrl+U also edit the text input above the list,
+    /// so they only jump while it's empty; Ctrl+Home/Ctrl+End always do.
+    pub fn from_key(key: &KeyWithModifier, input_is_empty: bool) -> Option<Self> {
+        match key.bare_key {
+            BareKey::PageUp if key.has_no_modifiers() => Some(Jump::PageUp),
+            BareKey::PageDown if key.has_no_modifiers() => Some(Jump::PageDown),
+            BareKey::Char('d') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                Some(Jump::HalfPageDown)
+            }
+            BareKey::Char('u') if key.has_modifiers(&[KeyModifier::Ctrl]) && input_is_empty => {
+                Some(Jump::HalfPageUp)
+            }
+            BareKey::Home if key.has_modifiers(&[KeyModifier::Ctrl]) => Some(Jump::First),
+            BareKey::End if key.has_modifiers(&[KeyModifier::Ctrl]) => Some(Jump::Last),
+            BareKey::Home if key.has_no_modifiers() && input_is_empty => Some(Jump::First),
+            BareKey::End if key.has_no_modifiers() && input_is_empty => Some(Jump::Last),
+            _ => None,
+        }
+    }
+
+    /// Index selected after jumping in a list of `len` rows that shows `page` rows at a time.
+    /// Unlike the arrow keys, jumps stop at the ends of the list instead of wrapping around.
+    pub fn apply(self, selected: Option<usize>, len: usize, page: usize) -> Option<usize> {
+        let last = len.checked_sub(1)?;
+        let page = page.max(1);
+        let half_page = (page / 2).max(1);
+        let current = selected.unwrap_or(0);
+        let index = match self {
+            Jump::PageUp => current.saturating_sub(page),
+            Jump::PageDown => current + page,
+            Jump::HalfPageUp => current.saturating_sub(half_page),
+            Jump::HalfPageDown => current + half_page,
+            Jump::First => 0,
+            Jump::Last => last,
+        };
+        Some(index.min(last))
+    }
+}
+
+/// Visible row picked by Alt+1 to Alt+9, counting from 0 for the first row on screen
+pub fn quick_select_slot(key: &KeyWithModifier) -> Option<usize> {
+    match key.bare_key {
+        BareKey::Char(c @ '1'..='9') if key.has_modifiers(&[KeyModifier::Alt]) => {
+            c.to_digit(10).map(|digit| digit as usize - 1)
+        }
+        _ => None,
+    }
+}
+
+/// Position of the selection within a list, e.g. `42/310`, or `-/310` without a selection
+pub fn position_indicator(selected: Option<usize>, len: usize) -> String {
+    match selected {
+        Some(index) => format!("{}/{}", index + 1, len),
+        None => format!("-/{}", len),
+    }
+}
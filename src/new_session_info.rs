@@ -3,12 +3,17 @@ use fuzzy_matcher::FuzzyMatcher;
 use std::path::PathBuf;
 use zellij_tile::prelude::*;
 
+use crate::navigation::{position_indicator, Jump};
+use crate::text_input::{InputEvent, TextInput};
+
 #[derive(Default)]
 pub struct NewSessionInfo {
-    name: String,
+    name: TextInput,
     layout_list: LayoutList,
     entering_new_session_info: EnteringState,
     pub new_session_folder: Option<PathBuf>,
+    /// Resurrectable session deleted when the new session is created, to start it afresh
+    replaced_session: Option<String>,
 }
 
 #[derive(Eq, PartialEq)]
@@ -25,11 +30,15 @@ impl Default for EnteringState {
 
 impl NewSessionInfo {
     pub fn name(&self) -> &str {
+        self.name.text()
+    }
+
+    pub fn name_input(&self) -> &TextInput {
         &self.name
     }
 
     pub fn set_name(&mut self, name: &str) {
-        self.name = name.to_string();
+        self.name.set_text(name);
     }
 
     pub fn set_folder(&mut self, folder: Option<PathBuf>) {
@@ -40,6 +49,14 @@ impl NewSessionInfo {
         self.new_session_folder.as_ref()
     }
 
+    pub fn set_replaced_session(&mut self, session_name: Option<String>) {
+        self.replaced_session = session_name;
+    }
+
+    pub fn replaced_session(&self) -> Option<&str> {
+        self.replaced_session.as_deref()
+    }
+
     pub fn advance_to_layout_selection(&mut self) {
         self.entering_new_session_info = EnteringState::EnteringLayoutSearch;
     }
@@ -50,7 +67,7 @@ impl NewSessionInfo {
         self.entering_new_session_info = EnteringState::EnteringName;
         self.update_layout_search_term();
     }
-    pub fn layout_search_term(&self) -> &str {
+    pub fn layout_search_input(&self) -> &TextInput {
         &self.layout_list.layout_search_term
     }
     pub fn entering_new_session_name(&self) -> bool {
@@ -59,24 +76,25 @@ impl NewSessionInfo {
     pub fn entering_layout_search_term(&self) -> bool {
         self.entering_new_session_info == EnteringState::EnteringLayoutSearch
     }
-    pub fn add_char(&mut self, character: char) {
+    pub fn handle_input_key(&mut self, key: &KeyWithModifier) {
         match self.entering_new_session_info {
             EnteringState::EnteringName => {
-                self.name.push(character);
+                self.name.handle_key(key);
             }
             EnteringState::EnteringLayoutSearch => {
-                self.layout_list.layout_search_term.push(character);
-                self.update_layout_search_term();
+                if self.layout_list.layout_search_term.handle_key(key) == InputEvent::Changed {
+                    self.update_layout_search_term();
+                }
             }
         }
     }
-    pub fn handle_backspace(&mut self) {
+    pub fn paste(&mut self, text: &str) {
         match self.entering_new_session_info {
             EnteringState::EnteringName => {
-                self.name.pop();
+                self.name.insert_str(text);
             }
             EnteringState::EnteringLayoutSearch => {
-                self.layout_list.layout_search_term.pop();
+                self.layout_list.layout_search_term.insert_str(text);
                 self.update_layout_search_term();
             }
         }
@@ -95,9 +113,6 @@ impl NewSessionInfo {
     }
     pub fn handle_key(&mut self, key: KeyWithModifier) {
         match key.bare_key {
-            BareKey::Backspace if key.has_no_modifiers() => {
-                self.handle_backspace();
-            }
             BareKey::Char('c') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
                 self.handle_break();
             }
@@ -126,16 +141,15 @@ impl NewSessionInfo {
                     }
                 }
             }
-            BareKey::Char(character) if key.has_no_modifiers() => {
-                self.add_char(character);
-            }
             BareKey::Up if key.has_no_modifiers() => {
                 self.move_selection_up();
             }
             BareKey::Down if key.has_no_modifiers() => {
                 self.move_selection_down();
             }
-            _ => {}
+            _ => {
+                self.handle_input_key(&key);
+            }
         }
     }
 
@@ -147,10 +161,14 @@ impl NewSessionInfo {
         let new_session_name = if self.name.is_empty() {
             None
         } else {
-            Some(self.name.as_str())
+            Some(self.name.text())
         };
 
         if new_session_name != current_session_name.as_ref().map(|s| s.as_str()) {
+            // Delete the session being replaced first, so its name is free
+            if let Some(replaced_session) = self.replaced_session.take() {
+                delete_dead_session(&replaced_session);
+            }
             match default_layout {
                 Some(layout_name) => {
                     // Find the layout by name
@@ -193,9 +211,13 @@ impl NewSessionInfo {
                 let new_session_name = if self.name.is_empty() {
                     None
                 } else {
-                    Some(self.name.as_str())
+                    Some(self.name.text())
                 };
                 if new_session_name != current_session_name.as_ref().map(|s| s.as_str()) {
+                    // Delete the session being replaced first, so its name is free
+                    if let Some(replaced_session) = self.replaced_session.take() {
+                        delete_dead_session(&replaced_session);
+                    }
                     match new_session_layout {
                         Some(new_session_layout) => {
                             let cwd = self.new_session_folder.as_ref().map(|c| PathBuf::from(c));
@@ -293,6 +315,42 @@ impl NewSessionInfo {
             (first_row_index_to_render, last_row_index_to_render)
         }
     }
+    /// Index of the first layout shown when `max_rows` fit, for mapping rendered rows back
+    pub fn first_layout_to_render(&self, max_rows: usize) -> usize {
+        self.range_to_render(
+            max_rows,
+            self.layouts_len(),
+            Some(self.layout_list.selected_layout_index),
+        )
+        .0
+    }
+    /// Select a layout, or layout search result while searching, by index
+    pub fn select_layout(&mut self, index: usize) {
+        if index <= self.layout_list.max_index() {
+            self.layout_list.selected_layout_index = index;
+        }
+    }
+    /// Move the layout selection by a jump, with `page` layouts shown at a time
+    pub fn jump_layout(&mut self, jump: Jump, page: usize) {
+        let selected = Some(self.layout_list.selected_layout_index);
+        if let Some(index) = jump.apply(selected, self.layouts_len(), page) {
+            self.layout_list.selected_layout_index = index;
+        }
+    }
+    /// Position of the selected layout, or layout search result while searching, e.g. `3/12`
+    pub fn layout_position(&self) -> String {
+        let len = self.layouts_len();
+        let selected = Some(self.layout_list.selected_layout_index).filter(|_| len > 0);
+        position_indicator(selected, len)
+    }
+    /// Number of layouts listed, only counting search results while searching
+    fn layouts_len(&self) -> usize {
+        if self.is_searching() {
+            self.layout_list.layout_search_results.len()
+        } else {
+            self.layout_count()
+        }
+    }
     pub fn is_searching(&self) -> bool {
         !self.layout_list.layout_search_term.is_empty()
     }
@@ -310,9 +368,10 @@ impl NewSessionInfo {
             let mut matches = vec![];
             let matcher = SkimMatcherV2::default().use_cache(true);
             for layout_info in &self.layout_list.layout_list {
-                if let Some((score, indices)) =
-                    matcher.fuzzy_indices(&layout_info.name(), &self.layout_list.layout_search_term)
-                {
+                if let Some((score, indices)) = matcher.fuzzy_indices(
+                    &layout_info.name(),
+                    self.layout_list.layout_search_term.text(),
+                ) {
                     matches.push(LayoutSearchResult {
                         layout_info: layout_info.clone(),
                         score,
@@ -325,10 +384,10 @@ impl NewSessionInfo {
             self.layout_list.clear_selection();
         }
     }
-    fn move_selection_up(&mut self) {
+    pub fn move_selection_up(&mut self) {
         self.layout_list.move_selection_up();
     }
-    fn move_selection_down(&mut self) {
+    pub fn move_selection_down(&mut self) {
         self.layout_list.move_selection_down();
     }
 }
@@ -338,7 +397,7 @@ pub struct LayoutList {
     layout_list: Vec<LayoutInfo>,
     layout_search_results: Vec<LayoutSearchResult>,
     selected_layout_index: usize,
-    layout_search_term: String,
+    layout_search_term: TextInput,
 }
 
 impl LayoutList {
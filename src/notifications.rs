@@ -0,0 +1,88 @@
+use zellij_tile::prelude::set_timeout;
+
+/// How important a notification is, which decides how long it stays and how it's styled
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    /// An action succeeded
+    Info,
+    /// An action couldn't be done, e.g. invalid input
+    Warning,
+    /// An action failed
+    Error,
+}
+
+impl Severity {
+    /// Seconds a notification stays visible
+    fn timeout(&self) -> f64 {
+        match self {
+            Severity::Info => 3.0,
+            Severity::Warning => 5.0,
+            Severity::Error => 8.0,
+        }
+    }
+}
+
+/// A message shown in the status line until it expires
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub severity: Severity,
+    pub message: String,
+    /// Timer that expires the notification
+    timer: u64,
+}
+
+/// Notifications currently shown, oldest first. Zellij reports a fired timer only by the
+/// duration it was set for, so pending timers are kept in the order they were set: timers of
+/// the same duration fire in that order.
+#[derive(Debug, Default)]
+pub struct Notifications {
+    active: Vec<Notification>,
+    /// Duration and id of every timer that hasn't fired yet
+    timers: Vec<(f64, u64)>,
+    next_timer: u64,
+}
+
+impl Notifications {
+    /// Show a notification and start the timer that expires it
+    pub fn push(&mut self, severity: Severity, message: String) {
+        // Repeating a message restarts it instead of stacking copies
+        self.active
+            .retain(|notification| notification.message != message);
+        let timer = self.next_timer;
+        self.next_timer += 1;
+        self.active.push(Notification {
+            severity,
+            message,
+            timer,
+        });
+        self.timers.push((severity.timeout(), timer));
+        set_timeout(severity.timeout());
+    }
+
+    /// Drop the notification of the timer that fired, returning whether one expired. Timers
+    /// of messages that were repeated since find nothing to drop.
+    pub fn expire(&mut self, elapsed: f64) -> bool {
+        let Some(position) = self
+            .timers
+            .iter()
+            .position(|(duration, _)| (duration - elapsed).abs() < 0.5)
+        else {
+            return false;
+        };
+        let (_, timer) = self.timers.remove(position);
+        let count = self.active.len();
+        self.active
+            .retain(|notification| notification.timer != timer);
+        self.active.len() != count
+    }
+
+    /// The most recent notification
+    pub fn latest(&self) -> Option<&Notification> {
+        self.active.last()
+    }
+
+    /// Number of notifications shown
+    pub fn len(&self) -> usize {
+        self.active.len()
+    }
+}
@@ -1,6 +1,10 @@
 use crate::session::types::SessionAction;
+use std::collections::BTreeMap;
 use std::time::Duration;
-use zellij_tile::prelude::{delete_dead_session, kill_sessions, switch_session, SessionInfo};
+use zellij_tile::prelude::{
+    delete_dead_session, kill_sessions, new_tab, open_command_pane_floating, switch_session,
+    CommandToRun, SessionInfo,
+};
 
 /// Manages session operations and state
 #[derive(Debug, Default)]
@@ -9,6 +13,8 @@ pub struct SessionManager {
     sessions: Vec<SessionInfo>,
     /// Session name pending deletion confirmation
     pending_deletion: Option<String>,
+    /// Resurrectable sessions pending confirmation of a bulk purge
+    pending_purge: Vec<String>,
     /// Resurrectable sessions
     resurrectable_sessions: Vec<(String, Duration)>,
 }
@@ -37,6 +43,13 @@ impl SessionManager {
         &self.resurrectable_sessions
     }
 
+    /// Get all resurrectable sessions, most recently exited first
+    pub fn resurrectable_sessions_by_age(&self) -> Vec<(String, Duration)> {
+        let mut sessions = self.resurrectable_sessions.clone();
+        sessions.sort_by_key(|(name, age)| (*age, name.clone()));
+        sessions
+    }
+
     /// Execute a session action
     pub fn execute_action(&mut self, action: SessionAction) {
         match action {
@@ -56,6 +69,26 @@ impl SessionManager {
                     kill_sessions(&[&name]);
                 }
             }
+            SessionAction::AttachInFloatingPane(name) => {
+                // Zellij refuses to attach from inside a session, so drop the
+                // variables that mark this pane as belonging to one
+                let command = CommandToRun::new_with_args(
+                    "env",
+                    vec![
+                        "-u",
+                        "ZELLIJ",
+                        "-u",
+                        "ZELLIJ_SESSION_NAME",
+                        "zellij",
+                        "attach",
+                        &name,
+                    ],
+                );
+                open_command_pane_floating(command, None, BTreeMap::new());
+            }
+            SessionAction::OpenTab { name, directory } => {
+                new_tab(Some(name), Some(directory));
+            }
         }
     }
 
@@ -81,24 +114,49 @@ impl SessionManager {
         self.pending_deletion.as_deref()
     }
 
-    /// Generate incremented session name for a base name
-    pub fn generate_incremented_name(&self, base_name: &str, separator: &str) -> String {
-        let base_exists = self.sessions.iter().any(|s| s.name == base_name)
+    /// Start confirmation of deleting several resurrectable sessions at once
+    pub fn start_purge(&mut self, session_names: Vec<String>) {
+        self.pending_purge = session_names;
+    }
+
+    /// Confirm the purge, deleting every pending session and returning how many there were
+    pub fn confirm_purge(&mut self) -> usize {
+        let session_names = std::mem::take(&mut self.pending_purge);
+        for name in &session_names {
+            delete_dead_session(name);
+        }
+        session_names.len()
+    }
+
+    /// Cancel the purge
+    pub fn cancel_purge(&mut self) {
+        self.pending_purge.clear();
+    }
+
+    /// Get resurrectable sessions pending a purge, empty if none is
+    pub fn pending_purge(&self) -> &[String] {
+        &self.pending_purge
+    }
+
+    /// Check if a running or resurrectable session has the name
+    fn is_name_taken(&self, name: &str) -> bool {
+        self.sessions.iter().any(|s| s.name == name)
             || self
                 .resurrectable_sessions
                 .iter()
-                .any(|(name, _)| name == base_name);
+                .any(|(session_name, _)| session_name == name)
+    }
 
-        if !base_exists {
+    /// Generate incremented session name for a base name
+    pub fn generate_incremented_name(&self, base_name: &str, separator: &str) -> String {
+        if !self.is_name_taken(base_name) {
             return base_name.to_string();
         }
 
         // Find the next available increment
         for counter in 2..=1000 {
             let candidate = format!("{}{}{}", base_name, separator, counter);
-            let exists = self.sessions.iter().any(|s| s.name == candidate);
-
-            if !exists {
+            if !self.is_name_taken(&candidate) {
                 return candidate;
             }
         }
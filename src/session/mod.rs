@@ -1,5 +1,9 @@
 pub mod manager;
+pub mod naming;
+pub mod tree;
 pub mod types;
+pub mod validation;
 
 pub use manager::SessionManager;
+pub use tree::{GroupBy, TreeRow, TreeRowKind};
 pub use types::{SessionAction, SessionItem};
@@ -0,0 +1,372 @@
+use super::validation;
+
+/// A value of a directory that can be used in a name template
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Placeholder {
+    /// Last segment of the path
+    Basename,
+    /// Segment before the basename
+    Parent,
+    /// Path relative to its base path, with the separator between segments
+    Relative,
+    /// Repository name from the git remote, or the repository root folder
+    Repo,
+    /// Owner of the repository from the git remote
+    Owner,
+    /// Current git branch
+    Branch,
+    /// The configured session separator
+    Sep,
+}
+
+impl Placeholder {
+    /// Look up a placeholder by its name
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "basename" => Some(Placeholder::Basename),
+            "parent" => Some(Placeholder::Parent),
+            "relative" => Some(Placeholder::Relative),
+            "repo" => Some(Placeholder::Repo),
+            "owner" => Some(Placeholder::Owner),
+            "branch" => Some(Placeholder::Branch),
+            "sep" => Some(Placeholder::Sep),
+            _ => None,
+        }
+    }
+
+    /// Check if the value comes from git
+    fn uses_git(&self) -> bool {
+        matches!(
+            self,
+            Placeholder::Repo | Placeholder::Owner | Placeholder::Branch
+        )
+    }
+}
+
+/// Part of a name template
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    /// Text used as is
+    Literal(String),
+    /// Placeholder replaced by a value of the directory
+    Placeholder(Placeholder),
+}
+
+/// How session names are generated for directories
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum NamingStrategy {
+    /// Basename, with parent folders added to tell apart conflicting and nested directories
+    #[default]
+    Smart,
+    /// Basename only
+    Basename,
+    /// `owner` and `repo` of the git remote
+    Remote,
+    /// Full path relative to the base path
+    Relative,
+    /// Parent folder and basename
+    Parent,
+    /// User template such as `{repo}{sep}{branch}`
+    Template(NameTemplate),
+}
+
+/// Literal text and placeholders making up a name template
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameTemplate {
+    segments: Vec<Segment>,
+}
+
+impl NamingStrategy {
+    /// Parse a strategy name, or a template when the value has placeholders
+    pub fn from_config(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.contains('{') {
+            return Self::parse_template(value);
+        }
+        match value {
+            "smart" => Some(NamingStrategy::Smart),
+            "basename" => Some(NamingStrategy::Basename),
+            "remote" => Some(NamingStrategy::Remote),
+            "relative" => Some(NamingStrategy::Relative),
+            "parent" => Some(NamingStrategy::Parent),
+            _ => None,
+        }
+    }
+
+    /// Parse a name template, rejecting unknown placeholders
+    fn parse_template(template: &str) -> Option<Self> {
+        let mut segments = Vec::new();
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                segments.push(Segment::Literal(rest[..start].to_string()));
+            }
+            let end = start + rest[start..].find('}')?;
+            let placeholder = Placeholder::from_name(&rest[start + 1..end])?;
+            segments.push(Segment::Placeholder(placeholder));
+            rest = &rest[end + 1..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_string()));
+        }
+        Some(NamingStrategy::Template(NameTemplate { segments }))
+    }
+
+    /// Check if names need the git information of directories
+    pub fn uses_git(&self) -> bool {
+        match self {
+            NamingStrategy::Remote => true,
+            NamingStrategy::Template(template) => template.segments.iter().any(|segment| {
+                matches!(segment, Segment::Placeholder(placeholder) if placeholder.uses_git())
+            }),
+            _ => false,
+        }
+    }
+
+    /// Name a directory, or `None` for the smart strategy and when a value the strategy
+    /// needs is unknown, e.g. a directory without a git remote
+    pub fn name(&self, directory: &NameContext, separator: &str) -> Option<String> {
+        let name = match self {
+            NamingStrategy::Smart => return None,
+            NamingStrategy::Basename => directory.basename().to_string(),
+            NamingStrategy::Remote => {
+                let (owner, repo) = directory.remote()?;
+                format!("{}{}{}", owner, separator, repo)
+            }
+            NamingStrategy::Relative => directory.relative(separator),
+            NamingStrategy::Parent => match directory.parent() {
+                Some(parent) => format!("{}{}{}", parent, separator, directory.basename()),
+                None => directory.basename().to_string(),
+            },
+            NamingStrategy::Template(template) => {
+                let mut name = String::new();
+                for segment in &template.segments {
+                    match segment {
+                        Segment::Literal(text) => name.push_str(text),
+                        Segment::Placeholder(placeholder) => {
+                            name.push_str(&directory.value(*placeholder, separator)?)
+                        }
+                    }
+                }
+                name
+            }
+        };
+        // Replace characters session names can't contain, e.g. slashes from branches like
+        // `feature/login` or control characters from a remote URL, without leaving separators
+        // at either end or next to each other
+        let name = name
+            .split(validation::is_illegal_char)
+            .map(|piece| {
+                piece
+                    .trim_start_matches(separator)
+                    .trim_end_matches(separator)
+            })
+            .filter(|piece| !piece.is_empty())
+            .collect::<Vec<_>>()
+            .join(separator);
+        (!name.is_empty()).then_some(name)
+    }
+}
+
+/// Naming strategy for all directories, with overrides for directories under a base path
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NamingRules {
+    default: NamingStrategy,
+    by_base_path: Vec<(String, NamingStrategy)>,
+}
+
+impl NamingRules {
+    /// Parse pipe-separated rules: a strategy for all directories and `path=strategy` entries
+    /// for directories under a base path, e.g. `smart|/home/user/work=remote`. Invalid entries,
+    /// such as unknown strategies or templates with an unclosed `{`, are skipped and returned.
+    pub fn from_config(value: &str) -> (Self, Vec<String>) {
+        let mut rules = Self::default();
+        let mut invalid_entries = Vec::new();
+        for entry in value.split('|').map(str::trim).filter(|e| !e.is_empty()) {
+            match entry.split_once('=') {
+                Some((path, strategy)) if path.trim().starts_with('/') => {
+                    match NamingStrategy::from_config(strategy) {
+                        Some(strategy) => {
+                            let path = path.trim().trim_end_matches('/').to_string();
+                            rules.by_base_path.push((path, strategy));
+                        }
+                        None => invalid_entries.push(entry.to_string()),
+                    }
+                }
+                _ => match NamingStrategy::from_config(entry) {
+                    Some(strategy) => rules.default = strategy,
+                    None => invalid_entries.push(entry.to_string()),
+                },
+            }
+        }
+        (rules, invalid_entries)
+    }
+
+    /// Strategy for a directory and the base path it was picked for, from the longest base
+    /// path containing the directory
+    pub fn strategy_for(&self, path: &str) -> (Option<&str>, &NamingStrategy) {
+        self.by_base_path
+            .iter()
+            .filter(|(base_path, _)| is_within(path, base_path))
+            .max_by_key(|(base_path, _)| base_path.len())
+            .map(|(base_path, strategy)| (Some(base_path.as_str()), strategy))
+            .unwrap_or((None, &self.default))
+    }
+
+    /// Check if any strategy needs the git information of directories
+    pub fn uses_git(&self) -> bool {
+        self.default.uses_git()
+            || self
+                .by_base_path
+                .iter()
+                .any(|(_, strategy)| strategy.uses_git())
+    }
+}
+
+/// Check if a path is a base path or inside it
+fn is_within(path: &str, base_path: &str) -> bool {
+    path.strip_prefix(base_path)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// What's known about a directory when naming it
+#[derive(Debug, Default)]
+pub struct NameContext<'a> {
+    /// Directory path with its base path stripped
+    pub relative_path: &'a str,
+    /// Root of the git repository containing the directory
+    pub repo_root: Option<&'a str>,
+    /// URL of the `origin` remote of that repository
+    pub remote_url: Option<&'a str>,
+    /// Current git branch
+    pub branch: Option<&'a str>,
+}
+
+impl NameContext<'_> {
+    fn segments(&self) -> Vec<&str> {
+        self.relative_path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect()
+    }
+
+    fn basename(&self) -> &str {
+        self.segments().last().copied().unwrap_or("root")
+    }
+
+    fn parent(&self) -> Option<&str> {
+        let segments = self.segments();
+        segments.len().checked_sub(2).map(|i| segments[i])
+    }
+
+    fn relative(&self, separator: &str) -> String {
+        self.segments().join(separator)
+    }
+
+    /// Owner and repository name of the git remote
+    fn remote(&self) -> Option<(&str, &str)> {
+        parse_remote_url(self.remote_url?)
+    }
+
+    fn value(&self, placeholder: Placeholder, separator: &str) -> Option<String> {
+        let value = match placeholder {
+            Placeholder::Basename => self.basename().to_string(),
+            Placeholder::Parent => self.parent()?.to_string(),
+            Placeholder::Relative => self.relative(separator),
+            Placeholder::Repo => match self.remote() {
+                Some((_, repo)) => repo.to_string(),
+                None => self.repo_root?.rsplit('/').next()?.to_string(),
+            },
+            Placeholder::Owner => self.remote()?.0.to_string(),
+            Placeholder::Branch => self.branch?.to_string(),
+            Placeholder::Sep => separator.to_string(),
+        };
+        (!value.is_empty()).then_some(value)
+    }
+}
+
+/// Owner and repository name of a remote URL such as `git@github.com:owner/repo.git` or
+/// `https://github.com/owner/repo`
+fn parse_remote_url(url: &str) -> Option<(&str, &str)> {
+    let path = url.trim().trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let mut parts = path.rsplit(['/', ':']);
+    let repo = parts.next().filter(|repo| !repo.is_empty())?;
+    let owner = parts.next().filter(|owner| !owner.is_empty())?;
+    Some((owner, repo))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_entries_are_skipped_without_dropping_valid_rules() {
+        let (rules, invalid) = NamingRules::from_config(
+            "parent|/home/user/work=remtoe|/home/user/oss={repo{sep}|/home/user/lw=remote",
+        );
+        assert_eq!(
+            invalid,
+            vec!["/home/user/work=remtoe", "/home/user/oss={repo{sep}"]
+        );
+        assert_eq!(rules.strategy_for("/tmp/x").1, &NamingStrategy::Parent);
+        assert_eq!(
+            rules.strategy_for("/home/user/lw/api"),
+            (Some("/home/user/lw"), &NamingStrategy::Remote)
+        );
+        assert_eq!(
+            rules.strategy_for("/home/user/work/api").1,
+            &NamingStrategy::Parent
+        );
+    }
+
+    #[test]
+    fn template_output_has_no_illegal_characters() {
+        let strategy = NamingStrategy::from_config("{repo}{sep}{branch}").unwrap();
+        let directory = NameContext {
+            relative_path: "work/api",
+            repo_root: Some("/home/user/work/api"),
+            remote_url: None,
+            branch: Some("feature/login\u{7}"),
+        };
+        let name = strategy.name(&directory, ".").unwrap();
+        assert_eq!(name, "api.feature.login");
+        assert!(validation::validate_name(&name, 100).is_ok());
+    }
+
+    #[test]
+    fn replaced_characters_leave_no_stray_separators() {
+        let name = |template: &str, branch: &str| {
+            let directory = NameContext {
+                relative_path: "work/api",
+                repo_root: Some("/home/user/work/api"),
+                remote_url: None,
+                branch: Some(branch),
+            };
+            NamingStrategy::from_config(template)
+                .unwrap()
+                .name(&directory, ".")
+        };
+        assert_eq!(
+            name("{branch}", "/feature//login/"),
+            Some("feature.login".to_string())
+        );
+        assert_eq!(
+            name("{repo}{sep}{branch}", "/login"),
+            Some("api.login".to_string())
+        );
+        assert_eq!(name("{branch}", "//"), None);
+    }
+
+    #[test]
+    fn remote_names_come_from_ssh_and_https_urls() {
+        assert_eq!(
+            parse_remote_url("git@github.com:owner/repo.git"),
+            Some(("owner", "repo"))
+        );
+        assert_eq!(
+            parse_remote_url("https://github.com/owner/repo/"),
+            Some(("owner", "repo"))
+        );
+    }
+}
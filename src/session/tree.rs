@@ -0,0 +1,303 @@
+use std::collections::{HashMap, HashSet};
+
+use super::SessionItem;
+
+/// How the main list groups directories and sessions
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GroupBy {
+    /// Flat list: sessions, then resurrectable sessions, then directories
+    #[default]
+    None,
+    /// Group directories under the configured base path containing them, or else their parent
+    Parent,
+    /// Group directories under their git repository root, falling back to `Parent`
+    Repo,
+}
+
+impl GroupBy {
+    /// Parse a grouping from its configuration name
+    pub fn from_config(value: &str) -> Option<Self> {
+        match value.trim() {
+            "none" => Some(GroupBy::None),
+            "parent" => Some(GroupBy::Parent),
+            "repo" => Some(GroupBy::Repo),
+            _ => None,
+        }
+    }
+}
+
+/// What a row of the main list shows
+#[derive(Debug, Clone)]
+pub enum TreeRowKind {
+    /// Header for a group whose root isn't itself a zoxide directory
+    Group { path: String, child_count: usize },
+    /// Heading of items that belong to no directory, collapsible like a group
+    Section { title: String, child_count: usize },
+    /// A session or directory
+    Item(SessionItem),
+}
+
+/// A row of the main list
+#[derive(Debug, Clone)]
+pub struct TreeRow {
+    /// Nesting depth, 0 for top-level rows
+    pub depth: usize,
+    /// What the row shows
+    pub kind: TreeRowKind,
+    /// Root path of the group the row belongs to, if it's part of one
+    pub group: Option<String>,
+    /// Whether the group is expanded, for rows that head a group
+    pub expanded: Option<bool>,
+}
+
+impl TreeRow {
+    /// A top-level row for an item, outside of any group
+    pub fn flat(item: SessionItem) -> Self {
+        Self {
+            depth: 0,
+            kind: TreeRowKind::Item(item),
+            group: None,
+            expanded: None,
+        }
+    }
+
+    /// Get the item shown on this row, if it isn't a group header or section heading
+    pub fn item(&self) -> Option<&SessionItem> {
+        match &self.kind {
+            TreeRowKind::Item(item) => Some(item),
+            TreeRowKind::Group { .. } | TreeRowKind::Section { .. } => None,
+        }
+    }
+}
+
+/// Find the root of the group a directory belongs to
+pub fn group_root(
+    path: &str,
+    group_by: GroupBy,
+    base_paths: &[String],
+    repo_roots: &HashMap<String, String>,
+) -> String {
+    if group_by == GroupBy::Repo {
+        if let Some(root) = repo_roots.get(path) {
+            return root.clone();
+        }
+    }
+
+    // Prefer the longest configured base path the directory is nested in
+    let base_path = base_paths
+        .iter()
+        .map(|base| base.trim_end_matches('/'))
+        .filter(|base| {
+            !base.is_empty()
+                && path
+                    .strip_prefix(base)
+                    .is_some_and(|rest| rest.len() > 1 && rest.starts_with('/'))
+        })
+        .max_by_key(|base| base.len());
+    if let Some(base_path) = base_path {
+        return base_path.to_string();
+    }
+
+    match path.trim_end_matches('/').rsplit_once('/') {
+        Some(("", _)) => "/".to_string(),
+        Some((parent, _)) => parent.to_string(),
+        None => path.to_string(),
+    }
+}
+
+/// A directory and the sessions belonging to it
+struct DirectoryNode<'a> {
+    /// Path of the directory
+    path: &'a str,
+    /// The directory row, or its first session when the directory row was merged into it
+    head: &'a SessionItem,
+    /// Sessions shown nested under the head row
+    sessions: Vec<&'a SessionItem>,
+}
+
+impl DirectoryNode<'_> {
+    /// Check if the directory has a running session
+    fn has_running_session(&self) -> bool {
+        self.head.is_session() || self.sessions.iter().any(|session| session.is_session())
+    }
+}
+
+/// Collect the directories of the item list along with their sessions. Sessions whose
+/// directory row isn't listed (see `Config::merge_directories`) take its place, heading
+/// the other sessions of that directory.
+fn directory_nodes(items: &[SessionItem]) -> Vec<DirectoryNode<'_>> {
+    let directory_paths: HashSet<&str> = items
+        .iter()
+        .filter_map(|item| match item {
+            SessionItem::Directory { path, .. } => Some(path.as_str()),
+            _ => None,
+        })
+        .collect();
+
+    let mut nodes: Vec<DirectoryNode> = Vec::new();
+    let mut node_indices: HashMap<&str, usize> = HashMap::new();
+    let mut sessions = Vec::new();
+    for item in items {
+        match item {
+            SessionItem::Directory { path, .. } => {
+                node_indices.insert(path, nodes.len());
+                nodes.push(DirectoryNode {
+                    path,
+                    head: item,
+                    sessions: Vec::new(),
+                });
+            }
+            SessionItem::ExistingSession { directory, .. }
+            | SessionItem::ResurrectableSession { directory, .. } => {
+                if directory_paths.contains(directory.as_str())
+                    || node_indices.contains_key(directory.as_str())
+                {
+                    sessions.push((directory.as_str(), item));
+                } else {
+                    node_indices.insert(directory, nodes.len());
+                    nodes.push(DirectoryNode {
+                        path: directory,
+                        head: item,
+                        sessions: Vec::new(),
+                    });
+                }
+            }
+        }
+    }
+
+    for (directory, session) in sessions {
+        if let Some(&index) = node_indices.get(directory) {
+            nodes[index].sessions.push(session);
+        }
+    }
+
+    nodes
+}
+
+/// Add the rows of a directory node: its head row, followed by its sessions unless the
+/// head row is a collapsed group header
+fn push_node(
+    rows: &mut Vec<TreeRow>,
+    node: &DirectoryNode,
+    depth: usize,
+    group: Option<&String>,
+    expanded: Option<bool>,
+) {
+    rows.push(TreeRow {
+        depth,
+        kind: TreeRowKind::Item(node.head.clone()),
+        group: group.cloned(),
+        expanded,
+    });
+    if expanded == Some(false) {
+        return;
+    }
+    for session in &node.sessions {
+        rows.push(TreeRow {
+            depth: depth + 1,
+            kind: TreeRowKind::Item((*session).clone()),
+            group: group.cloned(),
+            expanded: None,
+        });
+    }
+}
+
+/// Build the list with sessions nested under the directory they belong to, without grouping
+/// directories. Rows without sessions keep the order of the item list.
+pub fn build_nested(items: &[SessionItem]) -> Vec<TreeRow> {
+    let mut rows = Vec::new();
+    for node in directory_nodes(items) {
+        push_node(&mut rows, &node, 0, None, None);
+    }
+    rows
+}
+
+/// Build the grouped list from the flat item list. Sessions are nested under the directory
+/// they belong to, and directories under their group root. Groups are expanded according to
+/// `expanded`, defaulting to expanded when they contain a running session. Groups holding a
+/// single directory are shown as a plain top-level row.
+pub fn build_tree(
+    items: &[SessionItem],
+    root_of: impl Fn(&str) -> String,
+    expanded: &HashMap<String, bool>,
+) -> Vec<TreeRow> {
+    // Directories by group root, with groups ordered by their first directory
+    let mut groups: Vec<(String, Vec<DirectoryNode>)> = Vec::new();
+    let mut group_indices: HashMap<String, usize> = HashMap::new();
+    for node in directory_nodes(items) {
+        let root = root_of(node.path);
+        match group_indices.get(&root) {
+            Some(&index) => groups[index].1.push(node),
+            None => {
+                group_indices.insert(root.clone(), groups.len());
+                groups.push((root, vec![node]));
+            }
+        }
+    }
+
+    let mut rows = Vec::new();
+    for (root, nodes) in groups {
+        let has_running_session = nodes.iter().any(DirectoryNode::has_running_session);
+        let (headers, members): (Vec<DirectoryNode>, Vec<DirectoryNode>) =
+            nodes.into_iter().partition(|node| node.path == root);
+        let header = headers.first();
+
+        match (header, members.as_slice()) {
+            (Some(node), []) | (None, [node]) => {
+                push_node(&mut rows, node, 0, None, None);
+                continue;
+            }
+            _ => {}
+        }
+
+        let is_expanded = expanded.get(&root).copied().unwrap_or(has_running_session);
+
+        match header {
+            Some(header) => push_node(&mut rows, header, 0, Some(&root), Some(is_expanded)),
+            None => rows.push(TreeRow {
+                depth: 0,
+                kind: TreeRowKind::Group {
+                    path: root.clone(),
+                    child_count: members.len(),
+                },
+                group: Some(root.clone()),
+                expanded: Some(is_expanded),
+            }),
+        }
+
+        if is_expanded {
+            for member in &members {
+                push_node(&mut rows, member, 1, Some(&root), None);
+            }
+        }
+    }
+
+    rows
+}
+
+/// Add a section of items under a heading row. The section expands and collapses like a
+/// group, keyed by its title. Nothing is added for an empty section.
+pub fn push_section(rows: &mut Vec<TreeRow>, title: &str, items: Vec<SessionItem>, expanded: bool) {
+    if items.is_empty() {
+        return;
+    }
+    let group = Some(title.to_string());
+    rows.push(TreeRow {
+        depth: 0,
+        kind: TreeRowKind::Section {
+            title: title.to_string(),
+            child_count: items.len(),
+        },
+        group: group.clone(),
+        expanded: Some(expanded),
+    });
+    if !expanded {
+        return;
+    }
+    rows.extend(items.into_iter().map(|item| TreeRow {
+        depth: 1,
+        kind: TreeRowKind::Item(item),
+        group: group.clone(),
+        expanded: None,
+    }));
+}
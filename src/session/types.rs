@@ -7,9 +7,11 @@ pub enum SessionItem {
         directory: String,
         is_current: bool,
     },
-    /// A resurrectable session that can be restored
+    /// A resurrectable session that can be restored. The directory is empty when no zoxide
+    /// directory matches the session name.
     ResurrectableSession {
         name: String,
+        directory: String,
         duration: std::time::Duration,
     },
     /// A zoxide directory that can be used to create a new session
@@ -24,6 +26,26 @@ impl SessionItem {
     pub fn is_resurrectable_session(&self) -> bool {
         matches!(self, SessionItem::ResurrectableSession { .. })
     }
+    /// Check if this is a resurrectable session no zoxide directory matches
+    pub fn is_unmatched_resurrectable_session(&self) -> bool {
+        matches!(self, SessionItem::ResurrectableSession { directory, .. } if directory.is_empty())
+    }
+    /// Stable key identifying the item across reloads, e.g. in the search history
+    pub fn key(&self) -> String {
+        match self {
+            SessionItem::ExistingSession { name, .. }
+            | SessionItem::ResurrectableSession { name, .. } => format!("session:{}", name),
+            SessionItem::Directory { path, .. } => format!("directory:{}", path),
+        }
+    }
+    /// Get the name of the session, if this item is one
+    pub fn session_name(&self) -> Option<&str> {
+        match self {
+            SessionItem::ExistingSession { name, .. }
+            | SessionItem::ResurrectableSession { name, .. } => Some(name),
+            SessionItem::Directory { .. } => None,
+        }
+    }
 }
 
 /// Actions that can be performed on sessions
@@ -33,4 +55,8 @@ pub enum SessionAction {
     Switch(String),
     /// Kill an existing session
     Kill(String),
+    /// Attach to a session from a floating pane in the current session
+    AttachInFloatingPane(String),
+    /// Open a new tab in the current session, rooted at a directory
+    OpenTab { name: String, directory: String },
 }
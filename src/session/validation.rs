@@ -0,0 +1,100 @@
+use super::SessionManager;
+
+/// Problem that stops a name from being used for a new session
+#[derive(Debug, Clone, PartialEq)]
+pub enum NameIssue {
+    /// The name contains a character session names can't have
+    IllegalCharacter(char),
+    /// The name is longer than the socket path leaves room for
+    TooLong { length: usize, max: usize },
+    /// A running session has the name, so creating it would switch to that session
+    Running { suggestion: String },
+    /// A resurrectable session has the name, so creating it would resurrect that session
+    Resurrectable { suggestion: String },
+}
+
+impl NameIssue {
+    /// Message describing the issue
+    pub fn message(&self) -> String {
+        match self {
+            NameIssue::IllegalCharacter(c) if c.is_control() => {
+                "Session name cannot contain control characters".to_string()
+            }
+            NameIssue::IllegalCharacter(c) => format!("Session name cannot contain '{}'", c),
+            NameIssue::TooLong { length, max } => {
+                format!("Session name is {} bytes, at most {} fit", length, max)
+            }
+            NameIssue::Running { .. } => "A session with this name is already running".to_string(),
+            NameIssue::Resurrectable { .. } => {
+                "A resurrectable session has this name and would be resurrected".to_string()
+            }
+        }
+    }
+
+    /// Free name to use instead, for names taken by another session
+    pub fn suggestion(&self) -> Option<&str> {
+        match self {
+            NameIssue::Running { suggestion } | NameIssue::Resurrectable { suggestion } => {
+                Some(suggestion)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Check if a character can't be part of a session name
+pub fn is_illegal_char(c: char) -> bool {
+    c == '/' || c.is_control()
+}
+
+/// Check that a name can be used for a session at all: no illegal characters and no longer than
+/// `max_length`, the bytes the socket path leaves for it
+pub fn validate_name(name: &str, max_length: usize) -> Result<(), NameIssue> {
+    if let Some(c) = name.chars().find(|c| is_illegal_char(*c)) {
+        return Err(NameIssue::IllegalCharacter(c));
+    }
+    if name.len() > max_length {
+        return Err(NameIssue::TooLong {
+            length: name.len(),
+            max: max_length,
+        });
+    }
+    Ok(())
+}
+
+/// Check that a name can be used to create a new session: valid and not taken by a running or
+/// resurrectable session. `replaced_session` is a resurrectable session that gets deleted
+/// first, so its name is free. Empty names are fine, Zellij picks a random one.
+pub fn validate_new_session_name(
+    name: &str,
+    sessions: &SessionManager,
+    separator: &str,
+    replaced_session: Option<&str>,
+    max_length: usize,
+) -> Result<(), NameIssue> {
+    validate_name(name, max_length)?;
+    if name.is_empty() {
+        return Ok(());
+    }
+
+    let suggestion = || sessions.generate_incremented_name(name, separator);
+    if sessions
+        .sessions()
+        .iter()
+        .any(|session| session.name == name)
+    {
+        return Err(NameIssue::Running {
+            suggestion: suggestion(),
+        });
+    }
+    let is_resurrectable = sessions
+        .resurrectable_sessions()
+        .iter()
+        .any(|(session_name, _)| session_name == name);
+    if is_resurrectable && replaced_session != Some(name) {
+        return Err(NameIssue::Resurrectable {
+            suggestion: suggestion(),
+        });
+    }
+    Ok(())
+}
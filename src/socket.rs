@@ -0,0 +1,45 @@
+use std::collections::BTreeMap;
+use zellij_tile::prelude::{get_zellij_version, run_command};
+
+/// Context key marking a `RunCommandResult` as the socket directory of the Zellij server
+pub const SOCKET_DIR_CONTEXT: &str = "socket_dir";
+
+/// Longest Unix domain socket path in bytes, leaving room for the trailing NUL
+const MAX_SOCKET_PATH_LENGTH: usize = 107;
+
+/// Session name budget used when the socket directory is unknown and no
+/// `max_session_name_length` is configured, safe for most socket locations
+pub const DEFAULT_SESSION_NAME_BUDGET: usize = 29;
+
+/// Resolve the socket directory the way Zellij does: `ZELLIJ_SOCKET_DIR`, then
+/// `$XDG_RUNTIME_DIR/zellij` (not used on macOS), then `$TMPDIR/zellij-<uid>`
+const SOCKET_DIR_SCRIPT: &str = "if [ -n \"$ZELLIJ_SOCKET_DIR\" ]; then dir=\"$ZELLIJ_SOCKET_DIR\"; \
+     elif [ -n \"$XDG_RUNTIME_DIR\" ] && [ \"$(uname)\" != Darwin ]; then dir=\"$XDG_RUNTIME_DIR/zellij\"; \
+     else tmp=\"${TMPDIR:-/tmp}\"; dir=\"${tmp%/}/zellij-$(id -u)\"; fi; \
+     printf '%s' \"$dir\"";
+
+/// Request the socket directory of the Zellij server. The plugin runs in a
+/// WASM runtime without access to the host environment, so the directory is
+/// resolved by a shell on the host, which inherits the server's environment.
+/// The result arrives as a `RunCommandResult` carrying `SOCKET_DIR_CONTEXT`,
+/// which holds the version of the running Zellij.
+pub fn fetch_socket_dir() {
+    let mut context = BTreeMap::new();
+    context.insert(SOCKET_DIR_CONTEXT.to_string(), get_zellij_version());
+    run_command(&["sh", "-c", SOCKET_DIR_SCRIPT], context);
+}
+
+/// Longest session name in bytes whose socket fits in `socket_dir`. Zellij puts each
+/// session's socket in a folder named after its version, at `<socket_dir>/<version>/<name>`.
+/// `None` when either is unknown, or the socket directory leaves no room for a name.
+pub fn session_name_budget(socket_dir: &str, zellij_version: &str) -> Option<usize> {
+    let socket_dir = socket_dir.trim().trim_end_matches('/');
+    let zellij_version = zellij_version.trim();
+    if socket_dir.is_empty() || zellij_version.is_empty() {
+        return None;
+    }
+    let prefix_length = socket_dir.len() + 1 + zellij_version.len() + 1;
+    MAX_SOCKET_PATH_LENGTH
+        .checked_sub(prefix_length)
+        .filter(|budget| *budget > 0)
+}
@@ -1,12 +1,34 @@
-use std::collections::BTreeMap;
-use std::time::Duration;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
 use zellij_tile::prelude::*;
 
-use crate::config::Config;
+use crate::action_menu::{ActionMenu, MenuAction};
+use crate::clipboard;
+use crate::config::{Config, SECONDS_PER_DAY};
+use crate::navigation::{self, Jump};
 use crate::new_session_info::NewSessionInfo;
-use crate::session::{SessionAction, SessionItem, SessionManager};
+use crate::notifications::{Notifications, Severity};
+use crate::session::tree;
+use crate::session::validation::{self, NameIssue};
+use crate::session::{GroupBy, SessionAction, SessionItem, SessionManager, TreeRow, TreeRowKind};
+use crate::socket;
+use crate::store::{self, StoreFile};
+use crate::text_input::{InputEvent, TextInput};
+use crate::ui::hit_regions::{self, HitRegion, HitTarget};
+use crate::ui::{PluginRenderer, Theme};
+use crate::vim::{Vim, VimCommand, VimMode};
+use crate::zoxide::history::SearchHistory;
 use crate::zoxide::{SearchEngine, ZoxideDirectory};
 
+/// Longest time between two clicks on the same target that counts as a double click
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Heading of the resurrectable sessions no zoxide directory matches
+const UNMATCHED_RESURRECTABLE_SECTION: &str = "Other resurrectable sessions";
+
+/// Age in days offered for purging resurrectable sessions when `resurrectable_max_age` isn't set
+const DEFAULT_PURGE_DAYS: u64 = 30;
+
 /// The main plugin state
 pub struct PluginState {
     /// Plugin configuration
@@ -23,14 +45,50 @@ pub struct PluginState {
     active_screen: ActiveScreen,
     /// Error message to display
     error: Option<String>,
-    /// Color scheme
-    colors: Option<Palette>,
+    /// Lines the open dialog is scrolled down by
+    modal_scroll: usize,
+    /// Rows and columns of the plugin pane at the last render
+    viewport: (usize, usize),
+    /// Regions that react to clicks, from the last render
+    hit_regions: Vec<HitRegion>,
+    /// Target and time of the last click, for detecting double clicks
+    last_click: Option<(HitTarget, Instant)>,
     /// Current session name
     current_session_name: Option<String>,
     /// Request IDs for plugin communication
     request_ids: Vec<String>,
     /// Selected index in main list (when not searching)
     selected_index: Option<usize>,
+    /// Action menu for the selected item, if open
+    action_menu: Option<ActionMenu>,
+    /// Directories pinned to the top of the list
+    pinned_directories: Vec<String>,
+    /// Mode and pending keys of the main list, used when `vim_mode` is enabled
+    vim: Vim,
+    /// New name being entered for the current session, if renaming
+    rename_input: Option<TextInput>,
+    /// Directory path and session name being entered as its alias, if setting one
+    alias_input: Option<(String, TextInput)>,
+    /// Selected index in the resurrectable sessions screen
+    resurrectable_index: Option<usize>,
+    /// Age in days being entered for purging old resurrectable sessions, if purging
+    purge_input: Option<TextInput>,
+    /// Messages shown above the help line until they expire
+    notifications: Notifications,
+    /// Git repository root of each zoxide directory inside one, for grouping by repo
+    repo_roots: HashMap<String, String>,
+    /// Current git branch of each zoxide directory inside a repository
+    git_branches: HashMap<String, String>,
+    /// URL of the `origin` remote of each zoxide directory inside a repository that has one
+    git_remotes: HashMap<String, String>,
+    /// Session name aliases set from the action menu by path, taking precedence over configured ones
+    session_aliases: BTreeMap<String, String>,
+    /// Configured session name aliases that are valid session names, by path
+    config_aliases: BTreeMap<String, String>,
+    /// Groups the user expanded or collapsed, by root path
+    group_expansion: HashMap<String, bool>,
+    /// Longest session name that fits in the socket directory, once it's been discovered
+    socket_name_budget: Option<usize>,
 }
 
 /// Represents the different screens in the plugin
@@ -40,6 +98,8 @@ pub enum ActiveScreen {
     Main,
     /// New session creation screen
     NewSession,
+    /// All resurrectable sessions, sorted by age
+    Resurrectable,
 }
 
 impl Default for ActiveScreen {
@@ -58,10 +118,28 @@ impl Default for PluginState {
             new_session_info: NewSessionInfo::default(),
             active_screen: ActiveScreen::default(),
             error: None,
-            colors: None,
+            modal_scroll: 0,
+            viewport: (0, 0),
+            hit_regions: Vec::new(),
+            last_click: None,
             current_session_name: None,
             request_ids: Vec::new(),
             selected_index: None,
+            action_menu: None,
+            pinned_directories: Vec::new(),
+            vim: Vim::default(),
+            rename_input: None,
+            alias_input: None,
+            resurrectable_index: None,
+            purge_input: None,
+            notifications: Notifications::default(),
+            repo_roots: HashMap::new(),
+            git_branches: HashMap::new(),
+            git_remotes: HashMap::new(),
+            session_aliases: BTreeMap::new(),
+            config_aliases: BTreeMap::new(),
+            group_expansion: HashMap::new(),
+            socket_name_budget: None,
         }
     }
 }
@@ -70,6 +148,15 @@ impl PluginState {
     /// Initialize plugin with configuration
     pub fn initialize(&mut self, configuration: BTreeMap<String, String>) {
         self.config = Config::from_zellij_config(&configuration);
+        for warning in self.config.warnings.clone() {
+            self.notify(Severity::Warning, warning);
+        }
+        self.validate_config_aliases();
+        self.search_engine
+            .set_search_mode(self.config.search_mode, &[]);
+        self.search_engine.set_sort_policy(self.config.sort_policy);
+        self.search_engine
+            .set_rank_by_history(self.config.rank_by_history);
     }
 
     /// Update session information
@@ -95,20 +182,40 @@ impl PluginState {
     ) {
         self.session_manager
             .update_resurrectable_sessions(resurrectable_sessions);
+        // Keep the selection of the resurrectable sessions screen within the list
+        let len = self.session_manager.resurrectable_sessions().len();
+        self.resurrectable_index = self
+            .resurrectable_index
+            .map(|index| index.min(len.saturating_sub(1)))
+            .filter(|_| len > 0);
         self.update_search_if_needed();
     }
 
+    /// Get the zoxide directories, with their generated session names
+    pub fn zoxide_directories(&self) -> &[ZoxideDirectory] {
+        &self.zoxide_directories
+    }
+
     /// Update zoxide directories (managed separately from sessions)
     pub fn update_zoxide_directories(&mut self, directories: Vec<ZoxideDirectory>) {
         self.zoxide_directories = directories;
+        self.search_engine.set_rankings(
+            self.zoxide_directories
+                .iter()
+                .map(|dir| (dir.directory.clone(), dir.ranking))
+                .collect(),
+        );
         self.update_search_if_needed();
     }
 
     /// Handle key input
     pub fn handle_key(&mut self, key: KeyWithModifier) -> bool {
-        // Clear error on any key press
+        // Scroll a long error, and clear it on any other key press
         if self.error.is_some() {
-            self.error = None;
+            if !self.scroll_modal(&key) {
+                self.error = None;
+                self.modal_scroll = 0;
+            }
             return true;
         }
 
@@ -121,10 +228,191 @@ impl PluginState {
             return self.handle_deletion_confirmation(key, &session_name);
         }
 
+        if !self.session_manager.pending_purge().is_empty() {
+            return self.handle_purge_confirmation(key);
+        }
+
+        if self.action_menu.is_some() {
+            return self.handle_action_menu_key(key);
+        }
+
+        if self.rename_input.is_some() {
+            return self.handle_rename_key(key);
+        }
+
+        if self.alias_input.is_some() {
+            return self.handle_alias_key(key);
+        }
+
+        if self.purge_input.is_some() {
+            return self.handle_purge_prompt_key(key);
+        }
+
         match self.active_screen {
             ActiveScreen::Main => self.handle_main_screen_key(key),
             ActiveScreen::NewSession => self.handle_new_session_key(key),
+            ActiveScreen::Resurrectable => self.handle_resurrectable_key(key),
+        }
+    }
+
+    /// Handle mouse input: clicks select rows and press dialog buttons, double clicks activate
+    /// rows and the scroll wheel moves the selection
+    pub fn handle_mouse(&mut self, mouse: Mouse) -> bool {
+        match mouse {
+            Mouse::ScrollUp(_) => self.scroll(true),
+            Mouse::ScrollDown(_) => self.scroll(false),
+            Mouse::LeftClick(line, column) if line >= 0 => {
+                let Some(target) = hit_regions::target_at(&self.hit_regions, line as usize, column)
+                else {
+                    return false;
+                };
+                let now = Instant::now();
+                let is_double_click = matches!(
+                    self.last_click,
+                    Some((last_target, time))
+                        if last_target == target && now.duration_since(time) < DOUBLE_CLICK_INTERVAL
+                );
+                // A double click consumes both clicks so a third starts over
+                self.last_click = if is_double_click {
+                    None
+                } else {
+                    Some((target, now))
+                };
+                self.handle_click(target, is_double_click)
+            }
+            _ => false,
+        }
+    }
+
+    /// Move the selection of the list on screen by one row, or scroll the open dialog. Unlike
+    /// the arrow keys, scrolling never recalls previous searches.
+    fn scroll(&mut self, up: bool) -> bool {
+        let key = KeyWithModifier::new(if up { BareKey::Up } else { BareKey::Down });
+        if self.error.is_some()
+            || self.session_manager.pending_deletion().is_some()
+            || !self.session_manager.pending_purge().is_empty()
+            || self.rename_input.is_some()
+            || self.alias_input.is_some()
+            || self.purge_input.is_some()
+        {
+            return self.scroll_modal(&key);
+        }
+        if let Some(menu) = self.action_menu.as_mut() {
+            if up {
+                menu.move_selection_up();
+            } else {
+                menu.move_selection_down();
+            }
+            return true;
+        }
+        match (self.active_screen, up) {
+            (ActiveScreen::Main, true) => self.move_selection_up(),
+            (ActiveScreen::Main, false) => self.move_selection_down(),
+            (ActiveScreen::NewSession, true) => self.new_session_info.move_selection_up(),
+            (ActiveScreen::NewSession, false) => self.new_session_info.move_selection_down(),
+            (ActiveScreen::Resurrectable, _) => self.move_resurrectable_selection(up),
+        }
+        true
+    }
+
+    /// Select the clicked row, or activate it on a double click as Enter would
+    fn handle_click(&mut self, target: HitTarget, is_double_click: bool) -> bool {
+        match target {
+            HitTarget::ListRow(index) if self.active_screen == ActiveScreen::Resurrectable => {
+                if index < self.session_manager.resurrectable_sessions().len() {
+                    self.resurrectable_index = Some(index);
+                }
+            }
+            HitTarget::ListRow(index) => {
+                if self.search_engine.is_searching() {
+                    self.search_engine.select(index);
+                } else if index < self.list_rows().len() {
+                    self.selected_index = Some(index);
+                }
+            }
+            HitTarget::LayoutRow(index) => self.new_session_info.select_layout(index),
+            HitTarget::DialogLine(index) => {
+                if let Some(menu) = self.action_menu.as_mut() {
+                    menu.select(index);
+                }
+            }
+            HitTarget::Button(key) => return self.handle_key(KeyWithModifier::new(key)),
+        }
+        if is_double_click {
+            self.handle_key(KeyWithModifier::new(BareKey::Enter));
+        }
+        true
+    }
+
+    /// Rows of the list on screen, top to bottom, as recorded by the last render
+    fn visible_rows(&self) -> Vec<HitTarget> {
+        self.hit_regions
+            .iter()
+            .map(|region| region.target)
+            .filter(|target| matches!(target, HitTarget::ListRow(_) | HitTarget::LayoutRow(_)))
+            .collect()
+    }
+
+    /// Select the given visible row, as picked by Alt+1 to Alt+9
+    fn quick_select(&mut self, slot: usize) -> bool {
+        match self.visible_rows().get(slot) {
+            Some(&target) => self.handle_click(target, false),
+            None => false,
+        }
+    }
+
+    /// Move the selection of the main list, or of the search results while searching, by a
+    /// jump of pages or to either end
+    fn jump_selection(&mut self, jump: Jump) {
+        let page = self.visible_rows().len();
+        if self.active_screen == ActiveScreen::Resurrectable {
+            let len = self.session_manager.resurrectable_sessions().len();
+            if let Some(index) = jump.apply(self.resurrectable_index, len, page) {
+                self.resurrectable_index = Some(index);
+            }
+        } else if self.search_engine.is_searching() {
+            let len = self.search_engine.results().len();
+            if let Some(index) = jump.apply(self.search_engine.selected_index(), len, page) {
+                self.search_engine.select(index);
+            }
+        } else if let Some(index) = jump.apply(self.selected_index, self.list_rows().len(), page) {
+            self.selected_index = Some(index);
+        }
+    }
+
+    /// Remember the regions that react to clicks, recorded by the renderer
+    pub fn set_hit_regions(&mut self, regions: Vec<HitRegion>) {
+        self.hit_regions = regions;
+    }
+
+    /// Handle pasted text by inserting it into the active input
+    pub fn handle_paste(&mut self, text: &str) -> bool {
+        if self.error.is_some()
+            || self.session_manager.pending_deletion().is_some()
+            || !self.session_manager.pending_purge().is_empty()
+        {
+            return false;
         }
+
+        if let Some(menu) = self.action_menu.as_mut() {
+            menu.paste(text);
+        } else if let Some(input) = self.rename_input.as_mut() {
+            input.insert_str(text);
+        } else if let Some((_, input)) = self.alias_input.as_mut() {
+            input.insert_str(text);
+        } else if let Some(input) = self.purge_input.as_mut() {
+            input.insert_str(text);
+        } else {
+            match self.active_screen {
+                ActiveScreen::Main => {
+                    let items = self.combined_items(); // Always use full item list, not search results
+                    self.search_engine.paste(text, &items);
+                }
+                ActiveScreen::NewSession => self.new_session_info.paste(text),
+                ActiveScreen::Resurrectable => return false,
+            }
+        }
+        true
     }
 
     /// Get current screen
@@ -146,6 +434,91 @@ impl PluginState {
         }
     }
 
+    /// Get the rows of the main list when not searching, grouped if configured. Resurrectable
+    /// sessions without a zoxide directory follow under their own heading.
+    pub fn list_rows(&self) -> Vec<TreeRow> {
+        let (unmatched, items): (Vec<_>, Vec<_>) = self
+            .combined_items()
+            .into_iter()
+            .partition(SessionItem::is_unmatched_resurrectable_session);
+
+        let mut rows = if self.config.group_by == GroupBy::None {
+            if self.config.merge_directories {
+                tree::build_nested(&items)
+            } else {
+                items.into_iter().map(TreeRow::flat).collect()
+            }
+        } else {
+            tree::build_tree(
+                &items,
+                |path| {
+                    tree::group_root(
+                        path,
+                        self.config.group_by,
+                        &self.config.base_paths,
+                        &self.repo_roots,
+                    )
+                },
+                &self.group_expansion,
+            )
+        };
+
+        let expanded = self
+            .group_expansion
+            .get(UNMATCHED_RESURRECTABLE_SECTION)
+            .copied()
+            .unwrap_or(true);
+        tree::push_section(
+            &mut rows,
+            UNMATCHED_RESURRECTABLE_SECTION,
+            unmatched,
+            expanded,
+        );
+        rows
+    }
+
+    /// Mode of the main list, if `vim_mode` is enabled
+    pub fn vim_mode(&self) -> Option<VimMode> {
+        self.config.vim_mode.then(|| self.vim.mode())
+    }
+
+    /// Check if letters run vim commands instead of typing into the search
+    fn in_normal_mode(&self) -> bool {
+        self.vim_mode() == Some(VimMode::Normal)
+    }
+
+    /// Check if the main list is currently shown as a tree
+    pub fn is_tree_view(&self) -> bool {
+        self.config.group_by != GroupBy::None && !self.search_engine.is_searching()
+    }
+
+    /// Set the git repository roots, branches and remotes of zoxide directories
+    pub fn set_git_info(
+        &mut self,
+        repo_roots: HashMap<String, String>,
+        git_branches: HashMap<String, String>,
+        git_remotes: HashMap<String, String>,
+    ) {
+        self.repo_roots = repo_roots;
+        self.git_branches = git_branches;
+        self.git_remotes = git_remotes;
+    }
+
+    /// Get the root of the git repository containing a directory, if known
+    pub fn repo_root(&self, path: &str) -> Option<&str> {
+        self.repo_roots.get(path).map(|root| root.as_str())
+    }
+
+    /// Get the URL of the `origin` remote of a directory's repository, if known
+    pub fn git_remote(&self, path: &str) -> Option<&str> {
+        self.git_remotes.get(path).map(|remote| remote.as_str())
+    }
+
+    /// Get the current git branch of a directory, if known
+    pub fn git_branch(&self, path: &str) -> Option<&str> {
+        self.git_branches.get(path).map(|branch| branch.as_str())
+    }
+
     /// Combine sessions and zoxide directories for display
     fn combined_items(&self) -> Vec<SessionItem> {
         let mut items = Vec::new();
@@ -168,27 +541,61 @@ impl PluginState {
             }
         }
 
+        // Keep the sessions of each directory together, starting with the one named after it
+        if self.config.merge_directories {
+            let directory_index = |item: &SessionItem| match item {
+                SessionItem::ExistingSession { directory, .. } => self
+                    .zoxide_directories
+                    .iter()
+                    .position(|dir| &dir.directory == directory),
+                _ => None,
+            };
+            items.sort_by_key(|item| {
+                let name = item.session_name().unwrap_or_default().to_string();
+                (directory_index(item), name.len(), name)
+            });
+        }
+
         // Add resurrectable sessions if configured to show them
         if self.config.show_resurrectable_sessions {
+            let max_age = self.config.resurrectable_max_age.unwrap_or(Duration::MAX);
             for (name, duration) in self.session_manager.resurrectable_sessions() {
-                // Check if this session name matches any generated session name from zoxide directories
-                for zoxide_dir in &self.zoxide_directories {
-                    // Match exact name or incremented names (e.g., "project" matches "project.2", "project.3", etc.)
-                    if name == &zoxide_dir.session_name
-                        || self.is_incremented_session(name, &zoxide_dir.session_name)
-                    {
-                        items.push(SessionItem::ResurrectableSession {
-                            name: name.clone(),
-                            duration: duration.clone(),
-                        });
-                        break;
-                    }
+                if *duration > max_age {
+                    continue;
                 }
+                // Check if this session name matches any generated session name from zoxide directories.
+                // Sessions matching none, e.g. renamed ones, are kept without a directory.
+                let directory = self
+                    .zoxide_directories
+                    .iter()
+                    .find(|zoxide_dir| {
+                        // Match exact name or incremented names (e.g., "project" matches "project.2", "project.3", etc.)
+                        name == &zoxide_dir.session_name
+                            || self.is_incremented_session(name, &zoxide_dir.session_name)
+                    })
+                    .map(|zoxide_dir| zoxide_dir.directory.clone())
+                    .unwrap_or_default();
+                items.push(SessionItem::ResurrectableSession {
+                    name: name.clone(),
+                    directory,
+                    duration: duration.clone(),
+                });
             }
         }
 
-        // Then add all zoxide directories (always show directories, even if sessions exist)
-        for dir in &self.zoxide_directories {
+        // Then add all zoxide directories, with pinned directories first. Directories with
+        // running sessions are left out when merged into their session's row.
+        let (pinned, unpinned): (Vec<_>, Vec<_>) = self
+            .zoxide_directories
+            .iter()
+            .filter(|dir| {
+                !self.config.merge_directories
+                    || !items.iter().any(|item| {
+                        matches!(item, SessionItem::ExistingSession { directory, .. } if directory == &dir.directory)
+                    })
+            })
+            .partition(|dir| self.is_pinned(&dir.directory));
+        for dir in pinned.into_iter().chain(unpinned) {
             items.push(SessionItem::Directory {
                 path: dir.directory.clone(),
                 session_name: dir.session_name.clone(),
@@ -228,6 +635,81 @@ impl PluginState {
         &self.session_manager
     }
 
+    /// Get the open action menu (for UI rendering)
+    pub fn action_menu(&self) -> Option<&ActionMenu> {
+        self.action_menu.as_ref()
+    }
+
+    /// Get the issue stopping the name entered on the new session screen from being used
+    pub fn new_session_name_issue(&self) -> Option<NameIssue> {
+        validation::validate_new_session_name(
+            self.new_session_info.name(),
+            &self.session_manager,
+            &self.config.session_separator,
+            self.new_session_info.replaced_session(),
+            self.session_name_budget(),
+        )
+        .err()
+    }
+
+    /// Get the new session name being entered, if renaming (for UI rendering)
+    pub fn rename_input(&self) -> Option<&TextInput> {
+        self.rename_input.as_ref()
+    }
+
+    /// Get the directory and alias being entered for it, if setting one (for UI rendering)
+    pub fn alias_input(&self) -> Option<(&str, &TextInput)> {
+        self.alias_input
+            .as_ref()
+            .map(|(path, input)| (path.as_str(), input))
+    }
+
+    /// Get the age being entered for purging old resurrectable sessions (for UI rendering)
+    pub fn purge_input(&self) -> Option<&TextInput> {
+        self.purge_input.as_ref()
+    }
+
+    /// Get the selected index of the resurrectable sessions screen (for UI rendering)
+    pub fn resurrectable_index(&self) -> Option<usize> {
+        self.resurrectable_index
+    }
+
+    /// Check if a directory is pinned
+    pub fn is_pinned(&self, path: &str) -> bool {
+        self.pinned_directories.iter().any(|pinned| pinned == path)
+    }
+
+    /// Get the session name alias of a directory, set from the action menu or in the config
+    pub fn session_alias(&self, path: &str) -> Option<&str> {
+        self.session_aliases
+            .get(path)
+            .or_else(|| self.config_aliases.get(path))
+            .map(|name| name.as_str())
+    }
+
+    /// Load persisted data once its store file has been read
+    pub fn load_store_file(&mut self, file: StoreFile, contents: &str) {
+        match file {
+            StoreFile::Pins => {
+                self.pinned_directories = store::parse_lines(contents);
+            }
+            StoreFile::History => {
+                self.search_engine
+                    .set_history(SearchHistory::from_lines(&store::parse_lines(contents)));
+            }
+            StoreFile::Aliases => {
+                self.session_aliases = store::parse_lines(contents)
+                    .iter()
+                    .filter_map(|line| line.split_once('\t'))
+                    .map(|(path, name)| (path.to_string(), name.to_string()))
+                    .collect();
+                self.validate_config_aliases();
+                self.regenerate_session_names();
+            }
+        }
+        self.update_search_if_needed();
+    }
+
     /// Get selected index for main screen
     pub fn selected_index(&self) -> Option<usize> {
         if self.search_engine.is_searching() {
@@ -237,19 +719,46 @@ impl PluginState {
         }
     }
 
-    /// Get colors
-    pub fn colors(&self) -> Option<Palette> {
-        self.colors
-    }
-
-    /// Set colors
-    pub fn set_colors(&mut self, colors: Palette) {
-        self.colors = Some(colors);
+    /// Get the theme used for rendering
+    pub fn theme(&self) -> &Theme {
+        &self.config.theme
     }
 
     /// Show error message
     pub fn set_error(&mut self, error: String) {
         self.error = Some(error);
+        self.modal_scroll = 0;
+    }
+
+    /// Lines the open dialog is scrolled down by
+    pub fn modal_scroll(&self) -> usize {
+        self.modal_scroll
+    }
+
+    /// Remember the size of the plugin pane, which dialogs need to know how far they scroll
+    pub fn set_viewport(&mut self, rows: usize, cols: usize) {
+        self.viewport = (rows, cols);
+    }
+
+    /// Scroll the open dialog for Up/Down/PageUp/PageDown, returning whether the key scrolled
+    fn scroll_modal(&mut self, key: &KeyWithModifier) -> bool {
+        if !key.has_no_modifiers() {
+            return false;
+        }
+        let (rows, cols) = self.viewport;
+        let page = (rows / 2).max(1);
+        let scroll = match key.bare_key {
+            BareKey::Up => self.modal_scroll.saturating_sub(1),
+            BareKey::Down => self.modal_scroll + 1,
+            BareKey::PageUp => self.modal_scroll.saturating_sub(page),
+            BareKey::PageDown => self.modal_scroll + page,
+            _ => return false,
+        };
+        let max_scroll = PluginRenderer::overlay(self)
+            .map(|modal| modal.max_scroll(cols, rows))
+            .unwrap_or(0);
+        self.modal_scroll = scroll.min(max_scroll);
+        true
     }
 
     /// Get current error
@@ -257,32 +766,156 @@ impl PluginState {
         self.error.as_deref()
     }
 
+    /// Show a message in the status line until it expires
+    pub fn notify(&mut self, severity: Severity, message: String) {
+        self.notifications.push(severity, message);
+    }
+
+    /// Drop the notification whose timer fired, returning whether one expired
+    pub fn expire_notification(&mut self, elapsed: f64) -> bool {
+        self.notifications.expire(elapsed)
+    }
+
+    /// Get the notifications shown in the status line
+    pub fn notifications(&self) -> &Notifications {
+        &self.notifications
+    }
+
     /// Get current configuration
     pub fn config(&self) -> &Config {
         &self.config
     }
 
+    /// Record the socket directory of the Zellij server and its version, returning whether
+    /// the session name budget changed
+    pub fn set_socket_dir(&mut self, socket_dir: &str, zellij_version: &str) -> bool {
+        let budget = socket::session_name_budget(socket_dir, zellij_version);
+        let changed = budget.is_some() && budget != self.socket_name_budget;
+        if changed {
+            self.socket_name_budget = budget;
+            self.validate_config_aliases();
+        }
+        changed
+    }
+
+    /// Keep the configured aliases that can be used as session names, reporting the others:
+    /// names with illegal characters, longer than the name budget, or already the alias of
+    /// another directory, in the config or set from the action menu
+    fn validate_config_aliases(&mut self) {
+        let budget = self.session_name_budget();
+        let mut valid: BTreeMap<String, String> = BTreeMap::new();
+        let mut skipped = Vec::new();
+        for (path, alias) in &self.config.session_aliases {
+            if let Err(issue) = validation::validate_name(alias, budget) {
+                skipped.push(format!(
+                    "Skipped session alias of {}: {}",
+                    path,
+                    issue.message()
+                ));
+            } else if let Some((other, _)) = valid
+                .iter()
+                .chain(&self.session_aliases)
+                .find(|(other, other_alias)| *other_alias == alias && *other != path)
+            {
+                skipped.push(format!(
+                    "Skipped session alias of {}: '{}' is already the alias of {}",
+                    path, alias, other
+                ));
+            } else {
+                valid.insert(path.clone(), alias.clone());
+            }
+        }
+        self.config_aliases = valid;
+        for message in skipped {
+            self.notify(Severity::Warning, message);
+        }
+    }
+
+    /// Longest session name in bytes that fits in Zellij's socket path: computed from the
+    /// socket directory once known, otherwise `max_session_name_length` or a safe default
+    pub fn session_name_budget(&self) -> usize {
+        self.socket_name_budget
+            .or(self.config.max_session_name_length)
+            .unwrap_or(socket::DEFAULT_SESSION_NAME_BUDGET)
+    }
+
     /// Get selected item
     pub fn selected_item(&self) -> Option<SessionItem> {
         if self.search_engine.is_searching() {
             self.search_engine.selected_item().cloned()
         } else {
-            let items = self.display_items();
-            self.selected_index.and_then(|i| items.get(i).cloned())
+            let rows = self.list_rows();
+            self.selected_index
+                .and_then(|i| rows.get(i))
+                .and_then(|row| row.item().cloned())
         }
     }
 
     /// Handle main screen key input
     fn handle_main_screen_key(&mut self, key: KeyWithModifier) -> bool {
+        if self.in_normal_mode() {
+            if let Some(command) = self.vim.command(&key) {
+                self.run_vim_command(command);
+                return true;
+            }
+        } else if self.vim_mode().is_some()
+            && key.bare_key == BareKey::Esc
+            && key.has_no_modifiers()
+        {
+            self.vim.set_mode(VimMode::Normal);
+            return true;
+        }
+
+        // Keys that would edit the search move through the list while it isn't being typed in
+        let input_is_empty = self.in_normal_mode() || self.search_engine.search_term().is_empty();
+        if let Some(jump) = Jump::from_key(&key, input_is_empty) {
+            self.jump_selection(jump);
+            return true;
+        }
+        if let Some(slot) = navigation::quick_select_slot(&key) {
+            return self.quick_select(slot);
+        }
         match key.bare_key {
+            BareKey::Up
+                if key.has_no_modifiers()
+                    && !self.search_engine.is_searching()
+                    && self.selected_index.is_none() =>
+            {
+                // Nothing selected yet on an empty search: recall the previous search instead.
+                // The arrows then move through its results, Ctrl+p/Ctrl+n keep browsing history.
+                let items = self.combined_items(); // Always use full item list, not search results
+                if !self.search_engine.recall_previous(&items) {
+                    self.move_selection_up();
+                }
+                true
+            }
             BareKey::Up if key.has_no_modifiers() => {
                 self.move_selection_up();
                 true
             }
+            BareKey::Char('p') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                let items = self.combined_items(); // Always use full item list, not search results
+                self.search_engine.recall_previous(&items);
+                true
+            }
+            BareKey::Char('n') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                let items = self.combined_items(); // Always use full item list, not search results
+                self.search_engine.recall_next(&items);
+                true
+            }
             BareKey::Down if key.has_no_modifiers() => {
                 self.move_selection_down();
                 true
             }
+            // Groups only exist outside of search, where the arrows would move the search cursor
+            BareKey::Right if key.has_no_modifiers() && !self.search_engine.is_searching() => {
+                self.set_selected_group_expanded(true);
+                true
+            }
+            BareKey::Left if key.has_no_modifiers() && !self.search_engine.is_searching() => {
+                self.set_selected_group_expanded(false);
+                true
+            }
             BareKey::Enter if key.has_no_modifiers() => {
                 self.handle_item_selection();
                 true
@@ -291,18 +924,16 @@ impl PluginState {
                 self.handle_quick_session_creation();
                 true
             }
-            BareKey::Delete if key.has_no_modifiers() => {
-                self.handle_delete_key();
+            BareKey::Enter if key.has_modifiers(&[KeyModifier::Alt]) => {
+                self.open_action_menu();
                 true
             }
-            BareKey::Char(c) if key.has_no_modifiers() && c != '\n' => {
-                let items = self.combined_items(); // Always use full item list, not search results
-                self.search_engine.add_char(c, &items);
+            BareKey::Tab if key.has_no_modifiers() => {
+                self.open_action_menu();
                 true
             }
-            BareKey::Backspace if key.has_no_modifiers() => {
-                let items = self.combined_items(); // Always use full item list, not search results
-                self.search_engine.backspace(&items);
+            BareKey::Delete if key.has_no_modifiers() => {
+                self.handle_delete_key();
                 true
             }
             BareKey::Esc if key.has_no_modifiers() => {
@@ -318,18 +949,58 @@ impl PluginState {
                 hide_self();
                 false
             }
+            BareKey::Char('e') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                let items = self.combined_items(); // Always use full item list, not search results
+                let mode = self.search_engine.search_mode().next();
+                self.search_engine.set_search_mode(mode, &items);
+                true
+            }
             BareKey::Char('r') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
                 // reload zoxide directories
-                self.fetch_zoxide_directories();
+                self.reload_zoxide_directories();
                 true
             }
-            _ => false,
+            BareKey::Char('x') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                self.open_resurrectable_screen();
+                true
+            }
+            // Normal mode doesn't type into the search
+            _ if self.in_normal_mode() => false,
+            _ => {
+                // Everything else edits the search term
+                let items = self.combined_items(); // Always use full item list, not search results
+                self.search_engine.handle_key(&key, &items) != InputEvent::Ignored
+            }
         }
     }
 
     /// Handle new session screen key input
     fn handle_new_session_key(&mut self, key: KeyWithModifier) -> bool {
+        // Jumps move through the layout list, which is only shown while picking a layout
+        if self.new_session_info.entering_layout_search_term() {
+            let input_is_empty = !self.new_session_info.is_searching();
+            if let Some(jump) = Jump::from_key(&key, input_is_empty) {
+                let page = self.visible_rows().len();
+                self.new_session_info.jump_layout(jump, page);
+                return true;
+            }
+            if let Some(slot) = navigation::quick_select_slot(&key) {
+                return self.quick_select(slot);
+            }
+        }
         match key.bare_key {
+            // Names with issues can't be used until they're fixed, the issue is shown inline
+            BareKey::Enter if self.new_session_name_issue().is_some() => true,
+            BareKey::Tab if key.has_no_modifiers() => {
+                // Use the free name suggested for a taken one
+                if let Some(suggestion) = self
+                    .new_session_name_issue()
+                    .and_then(|issue| issue.suggestion().map(|s| s.to_string()))
+                {
+                    self.new_session_info.set_name(&suggestion);
+                }
+                true
+            }
             BareKey::Enter if key.has_no_modifiers() => {
                 // Handle session creation
                 self.new_session_info
@@ -339,17 +1010,11 @@ impl PluginState {
             }
             BareKey::Enter if key.has_modifiers(&[KeyModifier::Ctrl]) => {
                 // Quick session creation with default layout
-                if self.new_session_info.name().len() >= 108 {
-                    self.set_error("Session name must be shorter than 108 bytes".to_string());
-                } else if self.new_session_info.name().contains('/') {
-                    self.set_error("Session name cannot contain '/'".to_string());
-                } else {
-                    self.new_session_info.handle_quick_session_creation(
-                        &self.current_session_name,
-                        &self.config.default_layout,
-                    );
-                    self.active_screen = ActiveScreen::Main;
-                }
+                self.new_session_info.handle_quick_session_creation(
+                    &self.current_session_name,
+                    &self.config.default_layout,
+                );
+                self.active_screen = ActiveScreen::Main;
                 true
             }
             BareKey::Esc if key.has_no_modifiers() => {
@@ -383,26 +1048,537 @@ impl PluginState {
     }
 
     /// Handle deletion confirmation
-    fn handle_deletion_confirmation(&mut self, key: KeyWithModifier, _session_name: &str) -> bool {
+    fn handle_deletion_confirmation(&mut self, key: KeyWithModifier, session_name: &str) -> bool {
         match key.bare_key {
             BareKey::Char('y') | BareKey::Char('Y') if key.has_no_modifiers() => {
+                let is_resurrectable = self
+                    .session_manager
+                    .resurrectable_sessions()
+                    .iter()
+                    .any(|(name, _)| name == session_name);
                 self.session_manager.confirm_deletion();
+                self.modal_scroll = 0;
+                let message = if is_resurrectable {
+                    format!("Deleted resurrectable session '{}'", session_name)
+                } else {
+                    format!("Killed session '{}'", session_name)
+                };
+                self.notify(Severity::Info, message);
                 true
             }
             BareKey::Char('n') | BareKey::Char('N') | BareKey::Esc if key.has_no_modifiers() => {
                 self.session_manager.cancel_deletion();
+                self.modal_scroll = 0;
+                true
+            }
+            _ => self.scroll_modal(&key),
+        }
+    }
+
+    /// Handle action menu key input
+    fn handle_action_menu_key(&mut self, key: KeyWithModifier) -> bool {
+        match key.bare_key {
+            BareKey::Up if key.has_no_modifiers() => {
+                if let Some(menu) = self.action_menu.as_mut() {
+                    menu.move_selection_up();
+                }
+                true
+            }
+            BareKey::Down if key.has_no_modifiers() => {
+                if let Some(menu) = self.action_menu.as_mut() {
+                    menu.move_selection_down();
+                }
+                true
+            }
+            BareKey::Enter if key.has_no_modifiers() => {
+                if let Some(menu) = self.action_menu.take() {
+                    if let Some(action) = menu.selected_action() {
+                        self.record_search_selection(menu.item());
+                        self.run_menu_action(action, menu.item().clone());
+                    }
+                }
+                true
+            }
+            BareKey::Esc if key.has_no_modifiers() => {
+                match self.action_menu.as_mut() {
+                    Some(menu) if !menu.search_term().is_empty() => menu.clear_search(),
+                    _ => self.action_menu = None,
+                }
+                true
+            }
+            _ => match self.action_menu.as_mut() {
+                Some(menu) => menu.handle_input_key(&key) != InputEvent::Ignored,
+                None => false,
+            },
+        }
+    }
+
+    /// Handle resurrectable sessions screen key input
+    fn handle_resurrectable_key(&mut self, key: KeyWithModifier) -> bool {
+        if let Some(jump) = Jump::from_key(&key, true) {
+            self.jump_selection(jump);
+            return true;
+        }
+        if let Some(slot) = navigation::quick_select_slot(&key) {
+            return self.quick_select(slot);
+        }
+        let sessions = self.session_manager.resurrectable_sessions_by_age();
+        let selected = self
+            .resurrectable_index
+            .and_then(|index| sessions.get(index))
+            .map(|(name, _)| name.clone());
+        match key.bare_key {
+            BareKey::Up if key.has_no_modifiers() => {
+                self.move_resurrectable_selection(true);
+                true
+            }
+            BareKey::Down if key.has_no_modifiers() => {
+                self.move_resurrectable_selection(false);
+                true
+            }
+            BareKey::Enter if key.has_no_modifiers() => {
+                if let Some(name) = selected {
+                    self.session_manager
+                        .execute_action(SessionAction::Switch(name));
+                    hide_self();
+                }
+                true
+            }
+            BareKey::Delete if key.has_no_modifiers() => {
+                if let Some(name) = selected {
+                    self.session_manager.start_deletion(name);
+                }
+                true
+            }
+            BareKey::Char('r') if key.has_no_modifiers() => {
+                if let Some(name) = selected {
+                    let directory = self
+                        .combined_items()
+                        .into_iter()
+                        .find_map(|item| match item {
+                            SessionItem::ResurrectableSession {
+                                name: item_name,
+                                directory,
+                                ..
+                            } if item_name == name => Some(directory),
+                            _ => None,
+                        })
+                        .unwrap_or_default();
+                    self.start_resurrect_as(&name, &directory);
+                }
+                true
+            }
+            BareKey::Char('p') if key.has_no_modifiers() => {
+                self.open_purge_prompt();
+                true
+            }
+            BareKey::Esc if key.has_no_modifiers() => {
+                self.active_screen = ActiveScreen::Main;
+                true
+            }
+            BareKey::Char('c') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                hide_self();
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Move the selection of the resurrectable sessions screen by one row, wrapping around
+    fn move_resurrectable_selection(&mut self, up: bool) {
+        let Some(last) = self
+            .session_manager
+            .resurrectable_sessions()
+            .len()
+            .checked_sub(1)
+        else {
+            return;
+        };
+        self.resurrectable_index = Some(match (up, self.resurrectable_index) {
+            (true, Some(0) | None) => last,
+            (true, Some(index)) => index - 1,
+            (false, Some(index)) if index < last => index + 1,
+            _ => 0,
+        });
+    }
+
+    /// Show every resurrectable session, selecting the most recent one
+    fn open_resurrectable_screen(&mut self) {
+        self.active_screen = ActiveScreen::Resurrectable;
+        self.resurrectable_index = if self.session_manager.resurrectable_sessions().is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+    }
+
+    /// Ask for the age in days past which resurrectable sessions are purged, starting from
+    /// the configured maximum age
+    fn open_purge_prompt(&mut self) {
+        let days = self
+            .config
+            .resurrectable_max_age
+            .map(|age| age.as_secs() / SECONDS_PER_DAY)
+            .unwrap_or(DEFAULT_PURGE_DAYS);
+        let mut input = TextInput::default();
+        input.set_text(&days.to_string());
+        self.purge_input = Some(input);
+    }
+
+    /// Handle input of the age of resurrectable sessions to purge
+    fn handle_purge_prompt_key(&mut self, key: KeyWithModifier) -> bool {
+        let Some(input) = self.purge_input.as_mut() else {
+            return false;
+        };
+
+        match key.bare_key {
+            BareKey::Enter if key.has_no_modifiers() => {
+                let Ok(days) = input.text().trim().parse::<u64>() else {
+                    self.notify(Severity::Warning, "Enter the age in whole days".to_string());
+                    return true;
+                };
+                let Some(max_age) = days.checked_mul(SECONDS_PER_DAY).map(Duration::from_secs)
+                else {
+                    self.notify(Severity::Warning, format!("{} days is too long", days));
+                    return true;
+                };
+                let session_names: Vec<String> = self
+                    .session_manager
+                    .resurrectable_sessions_by_age()
+                    .into_iter()
+                    .filter(|(_, age)| *age > max_age)
+                    .map(|(name, _)| name)
+                    .collect();
+                self.purge_input = None;
+                if session_names.is_empty() {
+                    self.notify(
+                        Severity::Info,
+                        format!("No resurrectable sessions older than {} days", days),
+                    );
+                } else {
+                    self.session_manager.start_purge(session_names);
+                }
                 true
             }
+            BareKey::Esc if key.has_no_modifiers() => {
+                self.purge_input = None;
+                true
+            }
+            _ => input.handle_key(&key) != InputEvent::Ignored,
+        }
+    }
+
+    /// Handle confirmation of purging old resurrectable sessions
+    fn handle_purge_confirmation(&mut self, key: KeyWithModifier) -> bool {
+        match key.bare_key {
+            BareKey::Char('y') | BareKey::Char('Y') if key.has_no_modifiers() => {
+                let count = self.session_manager.confirm_purge();
+                self.modal_scroll = 0;
+                self.notify(
+                    Severity::Info,
+                    format!("Deleted {} resurrectable sessions", count),
+                );
+                true
+            }
+            BareKey::Char('n') | BareKey::Char('N') | BareKey::Esc if key.has_no_modifiers() => {
+                self.session_manager.cancel_purge();
+                self.modal_scroll = 0;
+                true
+            }
+            _ => self.scroll_modal(&key),
+        }
+    }
+
+    /// Run a command typed in vim normal mode
+    fn run_vim_command(&mut self, command: VimCommand) {
+        match command {
+            VimCommand::Down => self.move_selection_down(),
+            VimCommand::Up => self.move_selection_up(),
+            VimCommand::First => self.jump_selection(Jump::First),
+            VimCommand::Last => self.jump_selection(Jump::Last),
+            VimCommand::Search => self.vim.set_mode(VimMode::Insert),
+            VimCommand::Kill => self.handle_delete_key(),
+            VimCommand::Rename => self.run_selected_item_action(
+                &[MenuAction::Rename],
+                "Only the current session can be renamed",
+            ),
+            VimCommand::TogglePin => self.run_selected_item_action(
+                &[MenuAction::Pin, MenuAction::Unpin],
+                "Only directories can be pinned",
+            ),
+        }
+    }
+
+    /// Run the first of the given actions that's valid for the selected item, warning with
+    /// `unavailable` if none is
+    fn run_selected_item_action(&mut self, actions: &[MenuAction], unavailable: &str) {
+        let Some(item) = self.selected_item() else {
+            return;
+        };
+        let is_pinned = match &item {
+            SessionItem::Directory { path, .. } => self.is_pinned(path),
             _ => false,
+        };
+        let action = MenuAction::for_item(&item, is_pinned)
+            .into_iter()
+            .find(|action| actions.contains(action));
+        match action {
+            Some(action) => self.run_menu_action(action, item),
+            None => self.notify(Severity::Warning, unavailable.to_string()),
+        }
+    }
+
+    /// Open the action menu for the selected item
+    fn open_action_menu(&mut self) {
+        if let Some(item) = self.selected_item() {
+            let is_pinned = match &item {
+                SessionItem::Directory { path, .. } => self.is_pinned(path),
+                _ => false,
+            };
+            self.action_menu = Some(ActionMenu::for_item(item, is_pinned));
         }
     }
 
+    /// Run an action picked from the action menu
+    fn run_menu_action(&mut self, action: MenuAction, item: SessionItem) {
+        match (action, item) {
+            (MenuAction::Switch | MenuAction::Resurrect, item) => {
+                if let Some(name) = item.session_name() {
+                    self.session_manager
+                        .execute_action(SessionAction::Switch(name.to_string()));
+                    hide_self();
+                }
+            }
+            (MenuAction::AttachInFloatingPane, SessionItem::ExistingSession { name, .. }) => {
+                self.session_manager
+                    .execute_action(SessionAction::AttachInFloatingPane(name));
+                hide_self();
+            }
+            (
+                MenuAction::OpenTab,
+                SessionItem::ExistingSession {
+                    name, directory, ..
+                },
+            ) => {
+                self.session_manager
+                    .execute_action(SessionAction::OpenTab { name, directory });
+                hide_self();
+            }
+            (
+                MenuAction::OpenTab,
+                SessionItem::Directory {
+                    path, session_name, ..
+                },
+            ) => {
+                self.session_manager.execute_action(SessionAction::OpenTab {
+                    name: session_name,
+                    directory: path,
+                });
+                hide_self();
+            }
+            (MenuAction::Rename, SessionItem::ExistingSession { name, .. }) => {
+                let mut input = TextInput::default();
+                input.set_text(&name);
+                self.rename_input = Some(input);
+            }
+            (
+                MenuAction::ResurrectAs,
+                SessionItem::ResurrectableSession {
+                    name, directory, ..
+                },
+            ) => {
+                self.start_resurrect_as(&name, &directory);
+            }
+            (MenuAction::PurgeOld, _) => self.open_purge_prompt(),
+            (MenuAction::Kill | MenuAction::Delete, item) => {
+                if let Some(name) = item.session_name() {
+                    self.session_manager.start_deletion(name.to_string());
+                }
+            }
+            (
+                MenuAction::CreateWithLayout,
+                SessionItem::Directory {
+                    path, session_name, ..
+                },
+            ) => {
+                self.start_new_session(&session_name, &path);
+            }
+            (
+                MenuAction::QuickCreate,
+                SessionItem::Directory {
+                    path, session_name, ..
+                },
+            ) => {
+                let incremented_name = self
+                    .session_manager
+                    .generate_incremented_name(&session_name, &self.config.session_separator);
+                self.quick_create_session(incremented_name, Some(std::path::PathBuf::from(path)));
+            }
+            (MenuAction::Pin, SessionItem::Directory { path, .. }) => {
+                self.pinned_directories.push(path);
+                store::save(StoreFile::Pins, &self.pinned_directories);
+                self.update_search_if_needed();
+            }
+            (MenuAction::Unpin, SessionItem::Directory { path, .. }) => {
+                self.pinned_directories.retain(|pinned| pinned != &path);
+                store::save(StoreFile::Pins, &self.pinned_directories);
+                self.update_search_if_needed();
+            }
+            (
+                MenuAction::SetAlias,
+                SessionItem::Directory {
+                    path, session_name, ..
+                },
+            ) => {
+                let mut input = TextInput::default();
+                input.set_text(&session_name);
+                self.alias_input = Some((path, input));
+            }
+            (MenuAction::RemoveFromZoxide, SessionItem::Directory { path, .. }) => {
+                self.remove_zoxide_directory(&path);
+            }
+            (
+                MenuAction::CopyPath,
+                SessionItem::ExistingSession {
+                    directory: path, ..
+                }
+                | SessionItem::Directory { path, .. },
+            ) => {
+                self.copy_to_clipboard(&path, "path");
+            }
+            (MenuAction::CopySessionName, item) => {
+                if let Some(name) = item.session_name() {
+                    self.copy_to_clipboard(name, "session name");
+                }
+            }
+            (MenuAction::CopyAttachCommand, item) => {
+                if let Some(name) = item.session_name() {
+                    let command = format!("zellij attach {}", name);
+                    self.copy_to_clipboard(&command, "attach command");
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle rename prompt key input
+    fn handle_rename_key(&mut self, key: KeyWithModifier) -> bool {
+        let Some(input) = self.rename_input.as_mut() else {
+            return false;
+        };
+
+        match key.bare_key {
+            BareKey::Enter if key.has_no_modifiers() => {
+                let new_name = input.text().trim().to_string();
+                if new_name.is_empty() {
+                    self.notify(
+                        Severity::Warning,
+                        "Session name cannot be empty".to_string(),
+                    );
+                } else if let Err(issue) =
+                    validation::validate_name(&new_name, self.session_name_budget())
+                {
+                    self.notify(Severity::Warning, issue.message());
+                } else if self
+                    .session_manager
+                    .sessions()
+                    .iter()
+                    .any(|s| s.name == new_name && !s.is_current_session)
+                {
+                    self.notify(
+                        Severity::Warning,
+                        format!("A session named '{}' already exists", new_name),
+                    );
+                } else {
+                    rename_session(&new_name);
+                    self.rename_input = None;
+                }
+                true
+            }
+            BareKey::Esc if key.has_no_modifiers() => {
+                self.rename_input = None;
+                true
+            }
+            _ => input.handle_key(&key) != InputEvent::Ignored,
+        }
+    }
+
+    /// Handle alias prompt key input. An empty alias removes the one set from the action menu.
+    fn handle_alias_key(&mut self, key: KeyWithModifier) -> bool {
+        let Some((path, input)) = self.alias_input.as_mut() else {
+            return false;
+        };
+
+        match key.bare_key {
+            BareKey::Enter if key.has_no_modifiers() => {
+                let path = path.clone();
+                let alias = input.text().trim().to_string();
+                if alias.is_empty() {
+                    self.alias_input = None;
+                    if self.session_aliases.remove(&path).is_some() {
+                        self.save_session_aliases();
+                        self.notify(Severity::Info, format!("Removed alias of {}", path));
+                    }
+                } else if let Err(issue) =
+                    validation::validate_name(&alias, self.session_name_budget())
+                {
+                    self.notify(Severity::Warning, issue.message());
+                } else if let Some((other, _)) = self
+                    .zoxide_directories
+                    .iter()
+                    .filter(|dir| dir.directory != path)
+                    .filter_map(|dir| Some((&dir.directory, self.session_alias(&dir.directory)?)))
+                    .find(|(_, other_alias)| *other_alias == alias)
+                {
+                    self.notify(
+                        Severity::Warning,
+                        format!("'{}' is already the alias of {}", alias, other),
+                    );
+                } else {
+                    self.alias_input = None;
+                    self.session_aliases.insert(path, alias);
+                    self.save_session_aliases();
+                }
+                true
+            }
+            BareKey::Esc if key.has_no_modifiers() => {
+                self.alias_input = None;
+                true
+            }
+            _ => input.handle_key(&key) != InputEvent::Ignored,
+        }
+    }
+
+    /// Persist the aliases set from the action menu and rename directories to match
+    fn save_session_aliases(&mut self) {
+        let lines: Vec<String> = self
+            .session_aliases
+            .iter()
+            .map(|(path, name)| format!("{}\t{}", path, name))
+            .collect();
+        store::save(StoreFile::Aliases, &lines);
+        self.validate_config_aliases();
+        self.regenerate_session_names();
+    }
+
+    /// Copy text to the system clipboard using the configured copy command
+    fn copy_to_clipboard(&self, text: &str, description: &str) {
+        clipboard::copy(text, description, self.config.copy_command.as_deref());
+    }
+
+    /// Remove a directory from the zoxide database, reloading the list afterwards
+    fn remove_zoxide_directory(&mut self, path: &str) {
+        let mut context = BTreeMap::new();
+        context.insert("zoxide_remove".to_string(), path.to_string());
+        run_command(&["zoxide", "remove", path], context);
+    }
+
     /// Move selection up
     fn move_selection_up(&mut self) {
         if self.search_engine.is_searching() {
             self.search_engine.move_selection_up();
         } else {
-            let items_len = self.display_items().len();
+            let items_len = self.list_rows().len();
             if items_len == 0 {
                 return;
             }
@@ -424,7 +1600,7 @@ impl PluginState {
         if self.search_engine.is_searching() {
             self.search_engine.move_selection_down();
         } else {
-            let items_len = self.display_items().len();
+            let items_len = self.list_rows().len();
             if items_len == 0 {
                 return;
             }
@@ -441,8 +1617,49 @@ impl PluginState {
         }
     }
 
+    /// Expand or collapse the group of the selected row. Collapsing from inside a group
+    /// moves the selection to the group's header.
+    fn set_selected_group_expanded(&mut self, expanded: bool) {
+        let rows = self.list_rows();
+        let Some(row) = self.selected_index.and_then(|i| rows.get(i)) else {
+            return;
+        };
+        let Some(root) = row.group.clone() else {
+            return;
+        };
+        if row.expanded.is_none() && expanded {
+            return;
+        }
+
+        self.group_expansion.insert(root.clone(), expanded);
+        if !expanded {
+            self.selected_index = self
+                .list_rows()
+                .iter()
+                .position(|row| row.group.as_ref() == Some(&root) && row.expanded.is_some());
+        }
+    }
+
     /// Handle item selection (Enter key)
     fn handle_item_selection(&mut self) {
+        // Enter on a group header or section heading toggles it
+        if !self.search_engine.is_searching() {
+            let rows = self.list_rows();
+            if let Some(TreeRow {
+                kind: TreeRowKind::Group { .. } | TreeRowKind::Section { .. },
+                expanded: Some(expanded),
+                ..
+            }) = self.selected_index.and_then(|i| rows.get(i))
+            {
+                self.set_selected_group_expanded(!expanded);
+                return;
+            }
+        }
+
+        if let Some(item) = self.selected_item() {
+            self.record_search_selection(&item);
+        }
+
         // Get the selected item data before any mutable borrows
         let selected_item_data = self.selected_item().map(|item| match item {
             SessionItem::ExistingSession { name, .. } => (true, name, String::new()),
@@ -459,21 +1676,39 @@ impl PluginState {
                     .execute_action(SessionAction::Switch(name));
                 hide_self();
             } else {
-                // Create new session with incremented name
-                let incremented_name = self
-                    .session_manager
-                    .generate_incremented_name(&name, &self.config.session_separator);
-
-                // Set up new session creation
-                self.new_session_info.set_name(&incremented_name);
-                self.new_session_info
-                    .set_folder(Some(std::path::PathBuf::from(&path)));
-                self.new_session_info.advance_to_layout_selection();
-                self.active_screen = ActiveScreen::NewSession;
+                self.start_new_session(&name, &path);
             }
         }
     }
 
+    /// Open the new session screen for a directory, with an incremented session name
+    fn start_new_session(&mut self, session_name: &str, path: &str) {
+        let incremented_name = self
+            .session_manager
+            .generate_incremented_name(session_name, &self.config.session_separator);
+
+        // Set up new session creation
+        self.new_session_info.set_replaced_session(None);
+        self.new_session_info.set_name(&incremented_name);
+        self.new_session_info
+            .set_folder(Some(std::path::PathBuf::from(path)));
+        self.new_session_info.advance_to_layout_selection();
+        self.active_screen = ActiveScreen::NewSession;
+    }
+
+    /// Start creating a new session in place of a resurrectable one, keeping its name and
+    /// directory while a folder and layout are picked. The resurrectable session is deleted
+    /// once the new one is created.
+    fn start_resurrect_as(&mut self, session_name: &str, directory: &str) {
+        let folder = (!directory.is_empty()).then(|| std::path::PathBuf::from(directory));
+        self.new_session_info
+            .set_replaced_session(Some(session_name.to_string()));
+        self.new_session_info.set_name(session_name);
+        self.new_session_info.set_folder(folder);
+        self.new_session_info.advance_to_layout_selection();
+        self.active_screen = ActiveScreen::NewSession;
+    }
+
     /// Handle delete key
     fn handle_delete_key(&mut self) {
         // Get the selected item data before any mutable borrows
@@ -488,6 +1723,14 @@ impl PluginState {
         }
     }
 
+    /// Remember the item chosen for the current search and persist the history
+    fn record_search_selection(&mut self, item: &SessionItem) {
+        if self.search_engine.is_searching() {
+            self.search_engine.record_selection(item);
+            store::save(StoreFile::History, &self.search_engine.history().to_lines());
+        }
+    }
+
     /// Update search if currently searching
     fn update_search_if_needed(&mut self) {
         if self.search_engine.is_searching() {
@@ -549,11 +1792,35 @@ impl PluginState {
 
     /// Handle quick session creation from main screen
     fn handle_quick_session_creation(&mut self) {
-        use zellij_tile::prelude::{switch_session_with_cwd, switch_session_with_layout};
+        use zellij_tile::prelude::switch_session_with_cwd;
 
         // Get the selected item data or search term
         let (session_name, session_folder) = if let Some(selected_item) = self.selected_item() {
+            self.record_search_selection(&selected_item);
             match selected_item {
+                SessionItem::ExistingSession { directory, .. } if self.config.merge_directories => {
+                    // The directory row is merged into this one, so create a fresh session for it
+                    match self
+                        .zoxide_directories
+                        .iter()
+                        .find(|dir| dir.directory == directory)
+                    {
+                        Some(dir) => (
+                            self.session_manager.generate_incremented_name(
+                                &dir.session_name,
+                                &self.config.session_separator,
+                            ),
+                            Some(std::path::PathBuf::from(directory)),
+                        ),
+                        None => {
+                            self.notify(
+                                Severity::Warning,
+                                "Session directory is no longer in zoxide".to_string(),
+                            );
+                            return;
+                        }
+                    }
+                }
                 SessionItem::ExistingSession { name, .. } => {
                     // Switch to existing session
                     switch_session_with_cwd(Some(&name), None);
@@ -575,23 +1842,33 @@ impl PluginState {
                 }
             }
         } else {
-            self.set_error("Please select a directory".to_string());
+            self.notify(Severity::Warning, "Please select a directory".to_string());
             return;
         };
 
+        self.quick_create_session(session_name, session_folder);
+    }
+
+    /// Create a session with the default layout, if configured
+    fn quick_create_session(
+        &mut self,
+        session_name: String,
+        session_folder: Option<std::path::PathBuf>,
+    ) {
+        use zellij_tile::prelude::{switch_session_with_cwd, switch_session_with_layout};
+
         // Validate session name
-        if session_name.len() >= 108 {
-            self.set_error("Session name must be shorter than 108 bytes".to_string());
-            return;
-        }
-        if session_name.contains('/') {
-            self.set_error("Session name cannot contain '/'".to_string());
+        if let Err(issue) = validation::validate_name(&session_name, self.session_name_budget()) {
+            self.notify(Severity::Warning, issue.message());
             return;
         }
 
         // Check if session name is different from current session
         if Some(&session_name) == self.current_session_name.as_ref() {
-            self.set_error("Cannot create session with same name as current session".to_string());
+            self.notify(
+                Severity::Warning,
+                "Cannot create session with same name as current session".to_string(),
+            );
             return;
         }
 
@@ -634,3 +1911,39 @@ impl PluginState {
         hide_self();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_history() -> PluginState {
+        let mut state = PluginState::default();
+        state.update_zoxide_directories(
+            ["/home/user/api", "/home/user/web", "/home/user/docs"]
+                .iter()
+                .map(|path| ZoxideDirectory {
+                    ranking: 1.0,
+                    directory: path.to_string(),
+                    session_name: path.rsplit('/').next().unwrap().to_string(),
+                })
+                .collect(),
+        );
+        state.search_engine.set_history(SearchHistory::from_lines(&[
+            "api\tdir:/home/user/api".to_string(),
+        ]));
+        state
+    }
+
+    #[test]
+    fn scrolling_moves_the_selection_instead_of_recalling_searches() {
+        let mut state = state_with_history();
+        assert!(state.scroll(false));
+        assert_eq!(state.selected_index, Some(0));
+        assert_eq!(state.search_engine.search_term(), "");
+
+        let mut state = state_with_history();
+        assert!(state.scroll(true));
+        assert_eq!(state.selected_index, Some(state.list_rows().len() - 1));
+        assert_eq!(state.search_engine.search_term(), "");
+    }
+}
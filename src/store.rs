@@ -0,0 +1,78 @@
+use std::collections::BTreeMap;
+use zellij_tile::prelude::run_command;
+
+/// Context key marking a `RunCommandResult` as the contents of a store file
+pub const STORE_LOAD_CONTEXT: &str = "store_load";
+
+/// Context key marking a `RunCommandResult` as the outcome of writing a store file
+pub const STORE_SAVE_CONTEXT: &str = "store_save";
+
+/// Directory on the host where plugin data is persisted between sessions.
+/// The plugin's own `/data` folder is removed when the plugin unloads, so
+/// files are read and written on the host through `run_command` instead.
+const STORE_DIR: &str = "${XDG_DATA_HOME:-$HOME/.local/share}/zsm";
+
+/// A persisted plugin data file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StoreFile {
+    /// Pinned directory paths, one per line
+    Pins,
+    /// Accepted searches, one `term<TAB>item_key` pair per line, most recent first
+    History,
+    /// Session name aliases, one `path<TAB>name` pair per line
+    Aliases,
+}
+
+impl StoreFile {
+    /// Every store file, loaded once permissions are granted
+    pub const ALL: [StoreFile; 3] = [StoreFile::Pins, StoreFile::History, StoreFile::Aliases];
+
+    /// File name inside the store directory
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            StoreFile::Pins => "pins",
+            StoreFile::History => "history",
+            StoreFile::Aliases => "aliases",
+        }
+    }
+
+    /// Look up a store file by its file name
+    pub fn from_file_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|file| file.file_name() == name)
+    }
+}
+
+/// Request the contents of a store file. The result arrives as a
+/// `RunCommandResult` carrying `STORE_LOAD_CONTEXT` in its context.
+pub fn load(file: StoreFile) {
+    let mut context = BTreeMap::new();
+    context.insert(STORE_LOAD_CONTEXT.to_string(), file.file_name().to_string());
+    let script = format!("cat \"{}/$1\" 2>/dev/null || true", STORE_DIR);
+    run_command(&["sh", "-c", &script, "sh", file.file_name()], context);
+}
+
+/// Replace the contents of a store file. The outcome arrives as a
+/// `RunCommandResult` carrying `STORE_SAVE_CONTEXT` in its context.
+pub fn save(file: StoreFile, lines: &[String]) {
+    let mut context = BTreeMap::new();
+    context.insert(STORE_SAVE_CONTEXT.to_string(), file.file_name().to_string());
+    let contents = lines.join("\n");
+    let script = format!(
+        "mkdir -p \"{dir}\" && printf '%s\\n' \"$2\" > \"{dir}/$1\"",
+        dir = STORE_DIR
+    );
+    run_command(
+        &["sh", "-c", &script, "sh", file.file_name(), &contents],
+        context,
+    );
+}
+
+/// Split store file contents into its non-empty lines
+pub fn parse_lines(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_string())
+        .collect()
+}
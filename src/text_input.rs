@@ -0,0 +1,176 @@
+use zellij_tile::prelude::{BareKey, KeyModifier, KeyWithModifier};
+
+/// What a key press did to a text input
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputEvent {
+    /// The text was edited
+    Changed,
+    /// Only the cursor moved
+    Moved,
+    /// The key isn't handled by text inputs
+    Ignored,
+}
+
+/// Single-line text input with a cursor, shared by the search and name prompts
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    /// Current text
+    text: String,
+    /// Cursor position as a character index into the text
+    cursor: usize,
+}
+
+impl TextInput {
+    /// Get current text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Check if the input is empty
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Replace the text, moving the cursor to the end
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+        self.cursor = self.char_count();
+    }
+
+    /// Clear the text
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+    }
+
+    /// Insert a character at the cursor
+    pub fn insert_char(&mut self, c: char) {
+        let byte_index = self.byte_index(self.cursor);
+        self.text.insert(byte_index, c);
+        self.cursor += 1;
+    }
+
+    /// Insert pasted text at the cursor, dropping line breaks
+    pub fn insert_str(&mut self, text: &str) {
+        for c in text.chars().filter(|c| !c.is_control()) {
+            self.insert_char(c);
+        }
+    }
+
+    /// Delete the character before the cursor
+    pub fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            let byte_index = self.byte_index(self.cursor);
+            self.text.remove(byte_index);
+        }
+    }
+
+    /// Delete the word before the cursor, along with any whitespace after it
+    pub fn delete_word(&mut self) {
+        let chars: Vec<char> = self.text.chars().collect();
+        let mut start = self.cursor;
+        while start > 0 && chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+        while start > 0 && !chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+
+        let start_byte = self.byte_index(start);
+        let end_byte = self.byte_index(self.cursor);
+        self.text.replace_range(start_byte..end_byte, "");
+        self.cursor = start;
+    }
+
+    /// Move the cursor one character left
+    pub fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    /// Move the cursor one character right
+    pub fn move_right(&mut self) {
+        self.cursor = std::cmp::min(self.cursor + 1, self.char_count());
+    }
+
+    /// Move the cursor to the start of the text
+    pub fn move_home(&mut self) {
+        self.cursor = 0;
+    }
+
+    /// Move the cursor to the end of the text
+    pub fn move_end(&mut self) {
+        self.cursor = self.char_count();
+    }
+
+    /// Handle an editing key:
+    /// - Left/Right/Home/End move the cursor
+    /// - Backspace deletes a character, Ctrl+W or Alt+Backspace deletes a word
+    /// - Ctrl+U clears the line
+    /// - any other character is inserted
+    pub fn handle_key(&mut self, key: &KeyWithModifier) -> InputEvent {
+        match key.bare_key {
+            BareKey::Left if key.has_no_modifiers() => {
+                self.move_left();
+                InputEvent::Moved
+            }
+            BareKey::Right if key.has_no_modifiers() => {
+                self.move_right();
+                InputEvent::Moved
+            }
+            BareKey::Home if key.has_no_modifiers() => {
+                self.move_home();
+                InputEvent::Moved
+            }
+            BareKey::End if key.has_no_modifiers() => {
+                self.move_end();
+                InputEvent::Moved
+            }
+            BareKey::Backspace if key.has_no_modifiers() => {
+                self.backspace();
+                InputEvent::Changed
+            }
+            BareKey::Backspace if key.has_modifiers(&[KeyModifier::Alt]) => {
+                self.delete_word();
+                InputEvent::Changed
+            }
+            BareKey::Char('w') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                self.delete_word();
+                InputEvent::Changed
+            }
+            BareKey::Char('u') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                self.clear();
+                InputEvent::Changed
+            }
+            BareKey::Char(c) if key.has_no_modifiers() && c != '\n' => {
+                self.insert_char(c);
+                InputEvent::Changed
+            }
+            _ => InputEvent::Ignored,
+        }
+    }
+
+    /// Text to display, with a trailing `_` standing in for the cursor when it's at the end,
+    /// and the character index to highlight as the cursor when it's within the text
+    pub fn display(&self) -> (String, Option<usize>) {
+        if self.cursor >= self.char_count() {
+            (format!("{}_", self.text), None)
+        } else {
+            (self.text.clone(), Some(self.cursor))
+        }
+    }
+
+    /// Number of characters in the text
+    fn char_count(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    /// Byte offset of a character index
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(char_index)
+            .map(|(i, _)| i)
+            .unwrap_or(self.text.len())
+    }
+}
@@ -1,56 +1,52 @@
 use crate::new_session_info::NewSessionInfo;
+use crate::session::validation::NameIssue;
+use crate::ui::hit_regions::{HitRegion, HitTarget};
+use crate::ui::Theme;
 use zellij_tile::prelude::*;
 
-#[derive(Copy, Clone, Debug)]
-pub struct Colors;
-
-impl Colors {
-    pub fn new(_palette: Palette) -> Self {
-        Colors
-    }
-
-    pub fn shortcuts(&self, text: &str) -> Text {
-        Text::new(text).color_range(3, ..)
-    }
-}
-
 pub fn render_new_session_block(
     new_session_info: &NewSessionInfo,
-    colors: Colors,
+    name_issue: Option<&NameIssue>,
+    theme: &Theme,
     max_rows_of_new_session_block: usize,
     max_cols_of_new_session_block: usize,
     x: usize,
     y: usize,
-) {
-    let _enter = colors.shortcuts("<ENTER>");
+) -> Vec<HitRegion> {
+    let mut regions = Vec::new();
+    render_session_name_status(new_session_info, name_issue, theme, x, y);
     if new_session_info.entering_new_session_name() {
         let prompt = "New session name:";
         let long_instruction = "when done, blank for random";
-        let new_session_name = new_session_info.name();
+        let (new_session_name, cursor) = new_session_info.name_input().display();
+        let name_len = new_session_name.chars().count();
         if max_cols_of_new_session_block > 70 {
             let session_name_text = Text::new(&format!(
-                "{} {}_ (<ENTER> {})",
+                "{} {} (<ENTER> {})",
                 prompt, new_session_name, long_instruction
-            ))
-            .color_range(3, ..prompt.len())
-            .color_range(
-                0,
-                prompt.len() + 1..prompt.len() + 1 + new_session_name.len(),
-            )
-            .color_range(
-                3,
-                prompt.len() + new_session_name.len() + 4
-                    ..prompt.len() + new_session_name.len() + 11,
+            ));
+            let session_name_text = theme.prompt.apply_range(session_name_text, ..prompt.len());
+            let session_name_text = theme.input.apply_range(
+                session_name_text,
+                prompt.len() + 1..prompt.len() + 1 + name_len,
+            );
+            let session_name_text = theme.key.apply_range(
+                session_name_text,
+                prompt.len() + name_len + 3..prompt.len() + name_len + 10,
             );
+            let session_name_text = theme.with_cursor(session_name_text, cursor, prompt.len() + 1);
             print_text_with_coordinates(session_name_text, x, y + 1, None, None);
         } else {
-            let session_name_text = Text::new(&format!("{} {}_ <ENTER>", prompt, new_session_name))
-                .color_range(3, ..prompt.len())
-                .color_range(
-                    0,
-                    prompt.len() + 1..prompt.len() + 1 + new_session_name.len(),
-                )
-                .color_range(3, prompt.len() + new_session_name.len() + 3..);
+            let session_name_text = Text::new(&format!("{} {} <ENTER>", prompt, new_session_name));
+            let session_name_text = theme.prompt.apply_range(session_name_text, ..prompt.len());
+            let session_name_text = theme.input.apply_range(
+                session_name_text,
+                prompt.len() + 1..prompt.len() + 1 + name_len,
+            );
+            let session_name_text = theme
+                .key
+                .apply_range(session_name_text, prompt.len() + name_len + 2..);
+            let session_name_text = theme.with_cursor(session_name_text, cursor, prompt.len() + 1);
             print_text_with_coordinates(session_name_text, x, y + 1, None, None);
         }
     } else if new_session_info.entering_layout_search_term() {
@@ -60,23 +56,25 @@ pub fn render_new_session_block(
             new_session_info.name()
         };
         let prompt = "New session name:";
+        let name_len = new_session_name.chars().count();
         let session_name_text = Text::new(&format!(
             "{} {} (Ctrl+<R> to correct)",
             prompt, new_session_name
-        ))
-        .color_range(2, ..prompt.len())
-        .color_range(
-            1,
-            prompt.len() + 1..prompt.len() + 1 + new_session_name.len(),
-        )
-        .color_range(
-            3,
-            prompt.len() + new_session_name.len() + 3..prompt.len() + new_session_name.len() + 12,
+        ));
+        let session_name_text = theme.prompt.apply_range(session_name_text, ..prompt.len());
+        let session_name_text = theme.input.apply_range(
+            session_name_text,
+            prompt.len() + 1..prompt.len() + 1 + name_len,
+        );
+        let session_name_text = theme.key.apply_range(
+            session_name_text,
+            prompt.len() + name_len + 3..prompt.len() + name_len + 12,
         );
         print_text_with_coordinates(session_name_text, x, y + 1, None, None);
 
-        render_layout_selection_list(
+        regions = render_layout_selection_list(
             new_session_info,
+            theme,
             max_rows_of_new_session_block.saturating_sub(8),
             max_cols_of_new_session_block,
             x,
@@ -85,44 +83,49 @@ pub fn render_new_session_block(
     }
     render_new_session_folder_prompt(
         new_session_info,
-        colors,
+        theme,
         x,
         (y + max_rows_of_new_session_block).saturating_sub(3),
         max_cols_of_new_session_block,
     );
+    regions
 }
 
 pub fn render_layout_selection_list(
     new_session_info: &NewSessionInfo,
+    theme: &Theme,
     max_rows_of_new_session_block: usize,
     max_cols_of_new_session_block: usize,
     x: usize,
     y: usize,
-) {
-    let layout_search_term = new_session_info.layout_search_term();
+) -> Vec<HitRegion> {
+    let (layout_search_term, cursor) = new_session_info.layout_search_input().display();
+    let search_len = layout_search_term.chars().count();
     let layout_indication_line = if max_cols_of_new_session_block > 73 {
-        Text::new(format!(
-            "New session layout: {}_ (Search and select from list, <ENTER> when done)",
+        let line = Text::new(format!(
+            "New session layout: {} (Search and select from list, <ENTER> when done)",
             layout_search_term
-        ))
-        .color_range(2, ..20)
-        .color_range(1, 20..20 + layout_search_term.len())
-        .color_range(
-            3,
-            52 + layout_search_term.len()..59 + layout_search_term.len(),
-        )
+        ));
+        theme
+            .key
+            .apply_range(line, 51 + search_len..58 + search_len)
     } else {
-        Text::new(format!(
-            "New session layout: {}_ <ENTER>",
+        let line = Text::new(format!(
+            "New session layout: {} <ENTER>",
             layout_search_term
-        ))
-        .color_range(2, ..20)
-        .color_range(1, 20..20 + layout_search_term.len())
-        .color_range(3, 22 + layout_search_term.len()..)
+        ));
+        theme.key.apply_range(line, 21 + search_len..)
     };
+    let layout_indication_line = theme.prompt.apply_range(layout_indication_line, ..20);
+    let layout_indication_line = theme
+        .input
+        .apply_range(layout_indication_line, 20..20 + search_len);
+    let layout_indication_line = theme.with_cursor(layout_indication_line, cursor, 20);
     print_text_with_coordinates(layout_indication_line, x, y + 1, None, None);
 
     let mut table = Table::new();
+    let mut regions = Vec::new();
+    let first_layout = new_session_info.first_layout_to_render(max_rows_of_new_session_block);
     for (i, (layout_info, indices, is_selected)) in new_session_info
         .layouts_to_render(max_rows_of_new_session_block)
         .into_iter()
@@ -133,20 +136,23 @@ pub fn render_layout_selection_list(
         if i > max_rows_of_new_session_block.saturating_sub(1) {
             break;
         } else {
-            let mut layout_cell = if is_builtin {
+            let layout_cell = if is_builtin {
                 Text::new(format!("{} (built-in)", layout_name))
                     .color_range(1, 0..layout_name.len())
                     .color_range(0, layout_name.len() + 1..)
-                    .color_indices(3, indices)
             } else {
-                Text::new(format!("{}", layout_name))
-                    .color_range(1, ..)
-                    .color_indices(3, indices)
+                Text::new(format!("{}", layout_name)).color_range(1, ..)
             };
+            let mut layout_cell = theme.highlight(layout_cell, indices);
             if is_selected {
-                layout_cell = layout_cell.selected();
+                layout_cell = theme.select(layout_cell);
             }
             table = table.add_styled_row(vec![layout_cell]);
+            regions.push(HitRegion::new(
+                y + 3 + i,
+                x..x + max_cols_of_new_session_block,
+                HitTarget::LayoutRow(first_layout + i),
+            ));
         }
     }
     print_table_with_coordinates(
@@ -156,11 +162,65 @@ pub fn render_layout_selection_list(
         Some(max_cols_of_new_session_block),
         Some(max_rows_of_new_session_block),
     );
+
+    // Position of the selected layout, on the line below the list
+    print_text_with_coordinates(
+        theme.hint(&new_session_info.layout_position()),
+        x,
+        y + 3 + max_rows_of_new_session_block,
+        None,
+        None,
+    );
+    regions
+}
+
+/// Render what creating the session would do with the entered name, or the issue stopping the
+/// name from being used
+pub fn render_session_name_status(
+    new_session_info: &NewSessionInfo,
+    name_issue: Option<&NameIssue>,
+    theme: &Theme,
+    x: usize,
+    y: usize,
+) {
+    let text = match name_issue {
+        Some(issue) => {
+            let message = match issue.suggestion() {
+                Some(suggestion) => {
+                    format!("✗ {} • <Tab> to use '{}'", issue.message(), suggestion)
+                }
+                None => format!("✗ {}", issue.message()),
+            };
+            let text = theme.error(&message);
+            match message.find("<Tab>") {
+                Some(start) => {
+                    let start = message[..start].chars().count();
+                    theme.key.apply_range(text, start..start + 5)
+                }
+                None => text,
+            }
+        }
+        None => {
+            let creates = match new_session_info.name() {
+                "" => "Creates a session with a random name".to_string(),
+                name => format!("Creates session '{}'", name),
+            };
+            let message = match new_session_info.replaced_session() {
+                Some(replaced) => format!(
+                    "{}, deleting resurrectable session '{}' first",
+                    creates, replaced
+                ),
+                None => creates,
+            };
+            theme.hint(&message)
+        }
+    };
+    print_text_with_coordinates(text, x, y, None, None);
 }
 
 pub fn render_new_session_folder_prompt(
     new_session_info: &NewSessionInfo,
-    _colors: Colors,
+    theme: &Theme,
     x: usize,
     y: usize,
     max_cols: usize,
@@ -169,46 +229,46 @@ pub fn render_new_session_folder_prompt(
         Some(folder) => {
             let short_folder_prompt = "New session folder:";
             let folder_path = folder.to_string_lossy();
-            if max_cols > short_folder_prompt.len() + folder_path.len() + 40 {
+            let path_len = folder_path.chars().count();
+            let folder_text = if max_cols > short_folder_prompt.len() + path_len + 40 {
                 let folder_text = Text::new(&format!(
                     "{} {} (Ctrl+<f> to change, Ctrl+<c> to clear)",
                     short_folder_prompt, folder_path
-                ))
-                .color_range(2, ..short_folder_prompt.len())
-                .color_range(
-                    1,
-                    short_folder_prompt.len() + 1
-                        ..short_folder_prompt.len() + 1 + folder_path.len(),
-                )
-                .color_range(
-                    3,
-                    short_folder_prompt.len() + folder_path.len() + 3
-                        ..short_folder_prompt.len() + folder_path.len() + 11,
-                )
-                .color_range(
-                    3,
-                    short_folder_prompt.len() + folder_path.len() + 23
-                        ..short_folder_prompt.len() + folder_path.len() + 31,
+                ));
+                let folder_text = theme.key.apply_range(
+                    folder_text,
+                    short_folder_prompt.len() + path_len + 3
+                        ..short_folder_prompt.len() + path_len + 11,
                 );
-                print_text_with_coordinates(folder_text, x, y + 1, None, None);
+                theme.key.apply_range(
+                    folder_text,
+                    short_folder_prompt.len() + path_len + 23
+                        ..short_folder_prompt.len() + path_len + 31,
+                )
             } else {
                 let folder_text =
-                    Text::new(&format!("{} {} Ctrl+<f>", short_folder_prompt, folder_path))
-                        .color_range(2, ..short_folder_prompt.len())
-                        .color_range(
-                            1,
-                            short_folder_prompt.len() + 1
-                                ..short_folder_prompt.len() + 1 + folder_path.len(),
-                        )
-                        .color_range(3, short_folder_prompt.len() + folder_path.len() + 2..);
-                print_text_with_coordinates(folder_text, x, y + 1, None, None);
-            }
+                    Text::new(&format!("{} {} Ctrl+<f>", short_folder_prompt, folder_path));
+                theme
+                    .key
+                    .apply_range(folder_text, short_folder_prompt.len() + path_len + 2..)
+            };
+            let folder_text = theme
+                .prompt
+                .apply_range(folder_text, ..short_folder_prompt.len());
+            let folder_text = theme.input.apply_range(
+                folder_text,
+                short_folder_prompt.len() + 1..short_folder_prompt.len() + 1 + path_len,
+            );
+            print_text_with_coordinates(folder_text, x, y + 1, None, None);
         }
         None => {
             let folder_prompt = "New session folder (optional):";
-            let folder_text = Text::new(&format!("{} Ctrl+<f> to select", folder_prompt))
-                .color_range(2, ..folder_prompt.len())
-                .color_range(3, folder_prompt.len() + 1..folder_prompt.len() + 9);
+            let folder_text = Text::new(&format!("{} Ctrl+<f> to select", folder_prompt));
+            let folder_text = theme.prompt.apply_range(folder_text, ..folder_prompt.len());
+            let folder_text = theme.key.apply_range(
+                folder_text,
+                folder_prompt.len() + 1..folder_prompt.len() + 9,
+            );
             print_text_with_coordinates(folder_text, x, y + 1, None, None);
         }
     }
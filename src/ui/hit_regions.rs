@@ -0,0 +1,44 @@
+use std::ops::Range;
+
+use zellij_tile::prelude::BareKey;
+
+/// What a click on a region of the screen acts on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HitTarget {
+    /// Row of the main list, by index into the list rows or search results
+    ListRow(usize),
+    /// Row of the layout list, by index into the layouts or layout search results
+    LayoutRow(usize),
+    /// Selectable line of a dialog, by index among those lines
+    DialogLine(usize),
+    /// Dialog button, clicking it presses its key
+    Button(BareKey),
+}
+
+/// Part of a screen line that reacts to clicks, recorded while rendering
+#[derive(Debug, Clone, PartialEq)]
+pub struct HitRegion {
+    pub line: usize,
+    pub columns: Range<usize>,
+    pub target: HitTarget,
+}
+
+impl HitRegion {
+    pub fn new(line: usize, columns: Range<usize>, target: HitTarget) -> Self {
+        Self {
+            line,
+            columns,
+            target,
+        }
+    }
+}
+
+/// Find the target at a screen position. Regions recorded later are drawn on top of earlier
+/// ones, so they win.
+pub fn target_at(regions: &[HitRegion], line: usize, column: usize) -> Option<HitTarget> {
+    regions
+        .iter()
+        .rev()
+        .find(|region| region.line == line && region.columns.contains(&column))
+        .map(|region| region.target)
+}
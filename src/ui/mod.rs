@@ -1,7 +1,11 @@
 pub mod components;
+pub mod hit_regions;
+pub mod modal;
 pub mod renderer;
+pub mod row_format;
 pub mod theme;
+pub mod truncate;
 
-pub use components::Colors;
 pub use renderer::PluginRenderer;
+pub use row_format::RowFormat;
 pub use theme::Theme;
@@ -0,0 +1,457 @@
+use std::ops::Range;
+
+use zellij_tile::prelude::{print_text_with_coordinates, BareKey, Text};
+
+use crate::text_input::TextInput;
+use crate::ui::hit_regions::{HitRegion, HitTarget};
+use crate::ui::theme::Style;
+use crate::ui::truncate::{display_width, pad_to_width, truncate_end, truncate_start, wrap};
+use crate::ui::Theme;
+
+/// Widest a dialog gets, so paragraphs wrap at a readable length
+const MAX_WIDTH: usize = 64;
+/// Narrowest a dialog gets while the screen allows it
+const MIN_WIDTH: usize = 30;
+
+/// Content of a modal dialog
+enum Block {
+    /// Text wrapped to the width of the dialog
+    Paragraph { text: String, style: Style },
+    /// Single line cut to the width of the dialog, with highlighted characters
+    Line {
+        text: String,
+        style: Style,
+        indices: Vec<usize>,
+        selected: bool,
+    },
+    /// Text input after a prompt, showing its end if it doesn't fit
+    Input {
+        prompt: String,
+        text: String,
+        cursor: Option<usize>,
+    },
+    /// Empty line
+    Blank,
+}
+
+/// Key hint at the bottom of the dialog
+#[derive(Clone)]
+struct Button {
+    keys: String,
+    label: String,
+    /// Key pressed by clicking the hint, if it's clickable
+    key: Option<BareKey>,
+}
+
+/// A single rendered row of the dialog body
+struct BodyLine {
+    text: String,
+    /// Style of the whole line
+    style: Style,
+    /// Characters matching a search
+    indices: Vec<usize>,
+    /// Character the text of an input starts at, after its prompt
+    input_start: Option<usize>,
+    cursor: Option<usize>,
+    selected: bool,
+    /// Index among the selectable lines, if the line is one
+    target: Option<usize>,
+}
+
+/// A bordered dialog centered over the plugin, with a title, wrapped and scrollable content
+/// and hints for its keys
+pub struct Modal {
+    title: String,
+    border: Style,
+    blocks: Vec<Block>,
+    buttons: Vec<Button>,
+    scroll: usize,
+    min_body_height: usize,
+}
+
+impl Modal {
+    /// Create an empty dialog with a title shown in its top border
+    pub fn new(title: &str, border: Style) -> Self {
+        Self {
+            title: title.to_string(),
+            border,
+            blocks: Vec::new(),
+            buttons: Vec::new(),
+            scroll: 0,
+            min_body_height: 0,
+        }
+    }
+
+    /// Add a paragraph of text, wrapped to the dialog width
+    pub fn paragraph(mut self, text: &str, style: Style) -> Self {
+        self.blocks.push(Block::Paragraph {
+            text: text.to_string(),
+            style,
+        });
+        self
+    }
+
+    /// Add a single line with the characters at `indices` highlighted, e.g. a list entry.
+    /// The content scrolls to keep a selected line visible.
+    pub fn line(mut self, text: &str, style: Style, indices: Vec<usize>, selected: bool) -> Self {
+        self.blocks.push(Block::Line {
+            text: text.to_string(),
+            style,
+            indices,
+            selected,
+        });
+        self
+    }
+
+    /// Add a text input after a prompt
+    pub fn input(mut self, prompt: &str, input: &TextInput) -> Self {
+        let (text, cursor) = input.display();
+        self.blocks.push(Block::Input {
+            prompt: prompt.to_string(),
+            text,
+            cursor,
+        });
+        self
+    }
+
+    /// Add an empty line
+    pub fn blank(mut self) -> Self {
+        self.blocks.push(Block::Blank);
+        self
+    }
+
+    /// Add a key hint shown at the bottom that presses `key` when clicked,
+    /// e.g. `("n/Esc", "Cancel", BareKey::Esc)`
+    pub fn button(mut self, keys: &str, label: &str, key: BareKey) -> Self {
+        self.buttons.push(Button {
+            keys: keys.to_string(),
+            label: label.to_string(),
+            key: Some(key),
+        });
+        self
+    }
+
+    /// Add a key hint shown at the bottom that isn't clickable, e.g. `("↑/↓", "Select")`
+    pub fn hint(mut self, keys: &str, label: &str) -> Self {
+        self.buttons.push(Button {
+            keys: keys.to_string(),
+            label: label.to_string(),
+            key: None,
+        });
+        self
+    }
+
+    /// Scroll the content down by a number of lines
+    pub fn scroll(mut self, scroll: usize) -> Self {
+        self.scroll = scroll;
+        self
+    }
+
+    /// Keep the body at least this many lines high, so it doesn't resize as content changes
+    pub fn min_body_height(mut self, height: usize) -> Self {
+        self.min_body_height = height;
+        self
+    }
+
+    /// Render the dialog centered in the given area, returning the regions that react to clicks:
+    /// its selectable lines and clickable buttons
+    pub fn render(
+        &self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        theme: &Theme,
+    ) -> Vec<HitRegion> {
+        let dialog_width = self.width(width);
+        let inner_width = dialog_width.saturating_sub(4);
+        let body = self.body(inner_width);
+        let body_height = self.body_height(body.len(), height);
+        let scroll = self.clamped_scroll(&body, body_height);
+        let footer_height = if self.buttons.is_empty() { 0 } else { 2 };
+        let dialog_height = body_height + footer_height + 2;
+        let dialog_x = x + width.saturating_sub(dialog_width) / 2;
+        let dialog_y = y + height.saturating_sub(dialog_height) / 2;
+
+        let columns = dialog_x..dialog_x + dialog_width;
+        let mut regions = Vec::new();
+        let mut lines = vec![self.top_border(dialog_width, theme)];
+        for row in 0..body_height {
+            let line = body.get(scroll + row);
+            if let Some(target) = line.and_then(|line| line.target) {
+                let line_y = dialog_y + lines.len();
+                regions.push(HitRegion::new(
+                    line_y,
+                    columns.clone(),
+                    HitTarget::DialogLine(target),
+                ));
+            }
+            lines.push(self.body_line(line, inner_width, theme));
+        }
+        if !self.buttons.is_empty() {
+            lines.push(self.body_line(None, inner_width, theme));
+            let buttons = self.buttons(&body, body_height);
+            for (span, button) in Self::button_spans(&buttons, inner_width) {
+                if let Some(key) = button.key {
+                    let start = dialog_x + 2 + span.start;
+                    regions.push(HitRegion::new(
+                        dialog_y + lines.len(),
+                        start..start + span.len(),
+                        HitTarget::Button(key),
+                    ));
+                }
+            }
+            lines.push(self.button_line(&buttons, inner_width, theme));
+        }
+        let indicator = (body.len() > body_height).then(|| {
+            format!(
+                " {}-{}/{} ",
+                scroll + 1,
+                (scroll + body_height).min(body.len()),
+                body.len()
+            )
+        });
+        lines.push(self.bottom_border(dialog_width, indicator.as_deref()));
+
+        for (i, line) in lines.into_iter().enumerate() {
+            print_text_with_coordinates(line, dialog_x, dialog_y + i, None, None);
+        }
+        regions
+    }
+
+    /// Furthest the content can be scrolled within the given area
+    pub fn max_scroll(&self, width: usize, height: usize) -> usize {
+        let lines = self.body(self.width(width).saturating_sub(4)).len();
+        lines.saturating_sub(self.body_height(lines, height))
+    }
+
+    /// Width of the dialog: wide enough for its content within the screen and `MAX_WIDTH`
+    fn width(&self, screen_width: usize) -> usize {
+        let content = self
+            .blocks
+            .iter()
+            .map(|block| match block {
+                Block::Paragraph { text, .. } => text.lines().map(display_width).max().unwrap_or(0),
+                Block::Line { text, .. } => display_width(text),
+                Block::Input { prompt, text, .. } => {
+                    display_width(prompt) + 1 + display_width(text)
+                }
+                Block::Blank => 0,
+            })
+            .chain([
+                display_width(&self.title) + 4,
+                display_width(&Self::button_text(&self.buttons)),
+            ])
+            .max()
+            .unwrap_or(0);
+        (content + 4)
+            .clamp(MIN_WIDTH, MAX_WIDTH)
+            .min(screen_width.saturating_sub(4))
+    }
+
+    /// Number of body lines shown, leaving room for the borders and buttons
+    fn body_height(&self, lines: usize, screen_height: usize) -> usize {
+        let chrome = if self.buttons.is_empty() { 2 } else { 4 };
+        lines
+            .max(self.min_body_height)
+            .min(screen_height.saturating_sub(chrome))
+    }
+
+    /// Scroll offset within bounds, moved so the focused line is visible
+    fn clamped_scroll(&self, body: &[BodyLine], body_height: usize) -> usize {
+        let max_scroll = body.len().saturating_sub(body_height);
+        let mut scroll = self.scroll.min(max_scroll);
+        if let Some(focus) = body.iter().position(|line| line.selected) {
+            if focus < scroll {
+                scroll = focus;
+            } else if focus >= scroll + body_height {
+                scroll = focus + 1 - body_height;
+            }
+        }
+        scroll
+    }
+
+    /// Lay the blocks out into lines of at most `inner_width` columns
+    fn body(&self, inner_width: usize) -> Vec<BodyLine> {
+        let plain = |text: String, style: Style| BodyLine {
+            text,
+            style,
+            indices: Vec::new(),
+            input_start: None,
+            cursor: None,
+            selected: false,
+            target: None,
+        };
+
+        let mut body = Vec::new();
+        let mut targets = 0..;
+        for block in &self.blocks {
+            match block {
+                Block::Paragraph { text, style } => {
+                    body.extend(
+                        wrap(text, inner_width)
+                            .into_iter()
+                            .map(|line| plain(line, *style)),
+                    );
+                }
+                Block::Line {
+                    text,
+                    style,
+                    indices,
+                    selected,
+                } => {
+                    let truncated = truncate_end(text, indices, inner_width);
+                    body.push(BodyLine {
+                        indices: truncated.indices,
+                        selected: *selected,
+                        target: targets.next(),
+                        ..plain(truncated.text, *style)
+                    });
+                }
+                Block::Input {
+                    prompt,
+                    text,
+                    cursor,
+                } => {
+                    // Track the cursor through truncation like a highlighted character
+                    let line = format!("{} {}", prompt, text);
+                    let input_start = prompt.chars().count() + 1;
+                    let cursor: Vec<usize> = cursor
+                        .map(|cursor| input_start + cursor)
+                        .into_iter()
+                        .collect();
+                    let truncated = truncate_start(&line, &cursor, inner_width);
+                    // Once the start is cut off, the prompt is gone and only the input is left
+                    let input_start = if truncated.text == line {
+                        input_start
+                    } else {
+                        0
+                    };
+                    body.push(BodyLine {
+                        input_start: Some(input_start),
+                        cursor: truncated.indices.first().copied(),
+                        ..plain(truncated.text, Style::PLAIN)
+                    });
+                }
+                Block::Blank => body.push(plain(String::new(), Style::PLAIN)),
+            }
+        }
+        body
+    }
+
+    /// Style a body line inside the side borders, or an empty one
+    fn body_line(&self, line: Option<&BodyLine>, inner_width: usize, theme: &Theme) -> Text {
+        let content = line.map(|line| line.text.as_str()).unwrap_or("");
+        let boxed = format!("│ {} │", pad_to_width(content, inner_width));
+        let len = boxed.chars().count();
+        let mut text = Text::new(&boxed);
+        if let Some(line) = line {
+            let content_end = 2 + line.text.chars().count();
+            text = line.style.apply_range(text, 2..content_end);
+            if let Some(input_start) = line.input_start {
+                text = theme.prompt.apply_range(text, 2..2 + input_start);
+                text = theme.input.apply_range(text, 2 + input_start..content_end);
+            }
+            let indices = line.indices.iter().map(|i| i + 2).collect();
+            text = theme.highlight(text, indices);
+            text = theme.with_cursor(text, line.cursor, 2);
+            if line.selected {
+                text = theme.select(text);
+            }
+        }
+        let text = self.border.apply_range(text, ..1);
+        self.border.apply_range(text, len - 1..)
+    }
+
+    /// Key hints, with one for scrolling if the content scrolls and has no selection that
+    /// the arrow keys move instead
+    fn buttons(&self, body: &[BodyLine], body_height: usize) -> Vec<Button> {
+        let mut buttons = self.buttons.clone();
+        if body.len() > body_height && !body.iter().any(|line| line.selected) {
+            buttons.push(Button {
+                keys: "↑/↓".to_string(),
+                label: "Scroll".to_string(),
+                key: None,
+            });
+        }
+        buttons
+    }
+
+    /// Key hints joined into a line, e.g. `y: Confirm • n/Esc: Cancel`
+    fn button_text(buttons: &[Button]) -> String {
+        buttons
+            .iter()
+            .map(|button| format!("{}: {}", button.keys, button.label))
+            .collect::<Vec<_>>()
+            .join(" • ")
+    }
+
+    /// Character span of each hint that is fully visible on a line of `inner_width` columns
+    fn button_spans(buttons: &[Button], inner_width: usize) -> Vec<(Range<usize>, &Button)> {
+        let text = Self::button_text(buttons);
+        let visible = if display_width(&text) > inner_width {
+            // Leave room for the ellipsis of the truncated line
+            inner_width.saturating_sub(3)
+        } else {
+            inner_width
+        };
+        let mut spans = Vec::new();
+        let mut start = 0;
+        for button in buttons {
+            let len = button.keys.chars().count() + 2 + button.label.chars().count();
+            if start + len > visible {
+                break;
+            }
+            spans.push((start..start + len, button));
+            start += len + 3;
+        }
+        spans
+    }
+
+    /// Line of key hints with the keys styled
+    fn button_line(&self, buttons: &[Button], inner_width: usize, theme: &Theme) -> Text {
+        let hints = truncate_end(&Self::button_text(buttons), &[], inner_width).text;
+        let boxed = format!("│ {} │", pad_to_width(&hints, inner_width));
+        let len = boxed.chars().count();
+        let mut text = theme.hint.apply_range(Text::new(&boxed), 2..len - 2);
+        for (span, button) in Self::button_spans(buttons, inner_width) {
+            let key_start = 2 + span.start;
+            text = theme
+                .key
+                .apply_range(text, key_start..key_start + button.keys.chars().count());
+        }
+        let text = self.border.apply_range(text, ..1);
+        self.border.apply_range(text, len - 1..)
+    }
+
+    /// Top border with the title in it
+    fn top_border(&self, dialog_width: usize, theme: &Theme) -> Text {
+        let inner = dialog_width.saturating_sub(2);
+        let title = truncate_end(&self.title, &[], inner.saturating_sub(4)).text;
+        let title_len = title.chars().count();
+        let line = if title.is_empty() {
+            format!("┌{}┐", "─".repeat(inner))
+        } else {
+            format!(
+                "┌─ {} {}┐",
+                title,
+                "─".repeat(inner.saturating_sub(display_width(&title) + 3))
+            )
+        };
+        let text = self.border.apply_range(Text::new(&line), ..3);
+        let text = theme.title.apply_range(text, 3..3 + title_len);
+        self.border.apply_range(text, 3 + title_len..)
+    }
+
+    /// Bottom border, with the visible range of lines when the content scrolls
+    fn bottom_border(&self, dialog_width: usize, indicator: Option<&str>) -> Text {
+        let inner = dialog_width.saturating_sub(2);
+        let indicator = indicator.unwrap_or("");
+        let line = format!(
+            "└{}{}─┘",
+            "─".repeat(inner.saturating_sub(display_width(indicator) + 1)),
+            indicator
+        );
+        self.border.apply(Text::new(&line))
+    }
+}
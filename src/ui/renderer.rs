@@ -1,80 +1,218 @@
 use zellij_tile::prelude::{
-    print_table_with_coordinates, print_text_with_coordinates, Palette, Table, Text,
+    print_table_with_coordinates, print_text_with_coordinates, BareKey, Table, Text,
 };
 
-use crate::session::SessionItem;
+use crate::action_menu::ActionMenu;
+use crate::navigation::position_indicator;
+use crate::notifications::Severity;
+use crate::session::{SessionItem, TreeRow, TreeRowKind};
 use crate::state::{ActiveScreen, PluginState};
-use crate::ui::{Colors, Theme};
+use crate::text_input::TextInput;
+use crate::ui::hit_regions::{HitRegion, HitTarget};
+use crate::ui::modal::Modal;
+use crate::ui::row_format::{RowFormat, RowValues};
+use crate::ui::theme::Style;
+use crate::ui::truncate::{display_width, truncate_middle, truncate_path};
+use crate::ui::Theme;
+use crate::vim::VimMode;
 
 /// Main renderer for the plugin UI
 pub struct PluginRenderer;
 
 impl PluginRenderer {
-    /// Render the main plugin interface
-    pub fn render(state: &PluginState, rows: usize, cols: usize) {
+    /// Render the main plugin interface, returning the regions that react to clicks
+    pub fn render(state: &PluginState, rows: usize, cols: usize) -> Vec<HitRegion> {
         let (x, y, width, height) = Self::calculate_main_size(rows, cols);
 
-        match state.active_screen() {
-            ActiveScreen::Main => {
-                Self::render_main_screen(state, x, y, width, height);
+        let regions = match state.active_screen() {
+            ActiveScreen::Main => Self::render_main_screen(state, x, y, width, height),
+            ActiveScreen::NewSession => Self::render_new_session_screen(state, x, y, width, height),
+            ActiveScreen::Resurrectable => {
+                Self::render_resurrectable_screen(state, x, y, width, height)
             }
-            ActiveScreen::NewSession => {
-                Self::render_new_session_screen(state, x, y, width, height);
-            }
-        }
+        };
 
-        // Render overlays
-        if let Some(error) = state.error() {
-            Self::render_error(error, x, y, width, height);
-        } else if let Some(session_name) = state.session_manager().pending_deletion() {
-            Self::render_deletion_confirmation(session_name, x, y, width, height, state.colors());
+        // Render overlays, which take all clicks while open
+        match Self::overlay(state) {
+            Some(modal) => modal.render(x, y, width, height, state.theme()),
+            None => regions,
         }
     }
 
     /// Render the main screen with directory/session list
-    fn render_main_screen(state: &PluginState, x: usize, y: usize, width: usize, height: usize) {
-        let theme = state.colors().map(Theme::new);
+    fn render_main_screen(
+        state: &PluginState,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> Vec<HitRegion> {
+        let theme = state.theme();
 
         // Render title
-        let title = if let Some(theme) = &theme {
-            theme.title("Zoxide Session Manager")
-        } else {
-            Text::new("Zoxide Session Manager").color_range(2, ..)
-        };
-        print_text_with_coordinates(title, x, y, None, None);
+        Self::render_title(state, "Zoxide Session Manager", x, y);
 
         // Render search indication
-        let search_term = state.search_engine().search_term();
-        let search_text = format!("Search: {}_", search_term);
-        let search_indication = if let Some(theme) = &theme {
-            theme.content(&search_text).color_range(2, ..7)
-        } else {
-            Text::new(&search_text).color_range(1, ..7)
-        };
+        let (search_term, cursor) = state.search_engine().input().display();
+        let search_prompt = format!("Search [{}]:", state.search_engine().search_mode().label());
+        let search_text = format!("{} {}", search_prompt, search_term);
+        let search_indication = theme
+            .prompt
+            .apply_range(theme.content(&search_text), ..search_prompt.len());
+        let search_indication =
+            theme.with_cursor(search_indication, cursor, search_prompt.len() + 1);
         print_text_with_coordinates(search_indication, x, y + 2, None, None);
 
         // Render main content
         let table_rows = height.saturating_sub(6);
         let table = if state.search_engine().is_searching() {
-            Self::render_search_results(state, table_rows, width, &theme)
+            Self::render_search_results(state, table_rows, width, theme)
         } else {
-            Self::render_all_items(state, table_rows, width, &theme)
+            Self::render_all_items(state, table_rows, width, theme)
         };
 
-        if state.display_items().is_empty() && !state.search_engine().is_searching() {
-            let no_dirs_text = if let Some(theme) = &theme {
-                theme.warning("No zoxide directories found. Make sure zoxide is installed and you have visited some directories.")
-            } else {
-                Text::new("No zoxide directories found. Make sure zoxide is installed and you have visited some directories.")
-                    .color_range(1, ..)
-            };
+        let mut regions = Vec::new();
+        if state.list_rows().is_empty() && !state.search_engine().is_searching() {
+            let no_dirs_text = theme.warning("No zoxide directories found. Make sure zoxide is installed and you have visited some directories.");
             print_text_with_coordinates(no_dirs_text, x, y + 4, None, None);
         } else {
             print_table_with_coordinates(table, x, y + 4, Some(width), Some(table_rows));
+
+            // Rows start below the table header
+            let row_count = if state.search_engine().is_searching() {
+                state.search_engine().results().len()
+            } else {
+                state.list_rows().len()
+            };
+            let (first_row, last_row) =
+                Self::calculate_render_range(table_rows, row_count, state.selected_index());
+            for i in first_row..last_row.min(row_count) {
+                let line = y + 5 + i - first_row;
+                regions.push(HitRegion::new(line, x..x + width, HitTarget::ListRow(i)));
+            }
+            let title_width = display_width("Zoxide Session Manager");
+            let position = position_indicator(state.selected_index(), row_count);
+            Self::render_position(state, &position, title_width, x, y, width);
         }
 
+        Self::render_notification(state, x, y + height.saturating_sub(2), width);
+
         // Render help text
-        Self::render_help_text(state, x, y + height.saturating_sub(1), &theme);
+        Self::render_help_text(state, x, y + height.saturating_sub(1), theme);
+        regions
+    }
+
+    /// Render the resurrectable sessions screen, listing every one of them by age
+    fn render_resurrectable_screen(
+        state: &PluginState,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> Vec<HitRegion> {
+        let theme = state.theme();
+        let title = "Resurrectable Sessions";
+        Self::render_title(state, title, x, y);
+
+        let sessions = state.session_manager().resurrectable_sessions_by_age();
+        let selected_index = state.resurrectable_index();
+        let position = position_indicator(selected_index, sessions.len());
+        Self::render_position(state, &position, display_width(title), x, y, width);
+
+        let max_age = state.config().resurrectable_max_age;
+        let description = match max_age {
+            Some(max_age) => format!(
+                "Newest first. Sessions older than {} are hidden from the main list.",
+                humantime::format_duration(max_age)
+            ),
+            None => "Newest first.".to_string(),
+        };
+        print_text_with_coordinates(theme.hint(&description), x, y + 2, None, None);
+
+        let mut regions = Vec::new();
+        if sessions.is_empty() {
+            let text = theme.warning("No resurrectable sessions.");
+            print_text_with_coordinates(text, x, y + 4, None, None);
+        } else {
+            let table_rows = height.saturating_sub(6);
+            let (first_row, last_row) =
+                Self::calculate_render_range(table_rows, sessions.len(), selected_index);
+            let mut table = Table::new().add_row(vec!["Session", "Exited"]);
+            let name_width = width.saturating_sub(24);
+            for (i, (name, age)) in sessions.iter().enumerate().take(last_row).skip(first_row) {
+                let age_style = match max_age {
+                    Some(max_age) if *age > max_age => theme.warning,
+                    _ => Style::PLAIN,
+                };
+                let name = truncate_middle(name, &[], name_width).text;
+                let age = format!("{} ago", humantime::format_duration(*age));
+                let mut cells = vec![
+                    theme.resurrectable.apply(theme.content(&name)),
+                    age_style.apply(theme.content(&age)),
+                ];
+                if Some(i) == selected_index {
+                    cells = cells.drain(..).map(|t| theme.select(t)).collect();
+                }
+                table = table.add_styled_row(cells);
+                let line = y + 5 + i - first_row;
+                regions.push(HitRegion::new(line, x..x + width, HitTarget::ListRow(i)));
+            }
+            print_table_with_coordinates(table, x, y + 4, Some(width), Some(table_rows));
+        }
+
+        Self::render_notification(state, x, y + height.saturating_sub(2), width);
+        let help_text =
+            "↑/↓: Navigate • Enter: Resurrect • r: Resurrect as… • Delete: Delete • p: Purge old sessions • Esc: Back";
+        print_text_with_coordinates(
+            theme.hint(help_text),
+            x,
+            y + height.saturating_sub(1),
+            None,
+            None,
+        );
+        regions
+    }
+
+    /// Render a screen title
+    fn render_title(state: &PluginState, title: &str, x: usize, y: usize) {
+        print_text_with_coordinates(state.theme().title(title), x, y, None, None);
+    }
+
+    /// Render the position of the selection right-aligned on the title line, if it fits next to
+    /// the title
+    fn render_position(
+        state: &PluginState,
+        position: &str,
+        title_width: usize,
+        x: usize,
+        y: usize,
+        width: usize,
+    ) {
+        if width > title_width + position.len() {
+            let position_x = x + width - position.len();
+            let text = state.theme().hint(position);
+            print_text_with_coordinates(text, position_x, y, None, None);
+        }
+    }
+
+    /// Render the latest notification, with a count of the others still shown
+    fn render_notification(state: &PluginState, x: usize, y: usize, width: usize) {
+        let theme = state.theme();
+        let Some(notification) = state.notifications().latest() else {
+            return;
+        };
+        let (icon, style) = match notification.severity {
+            Severity::Info => ("✓", theme.success),
+            Severity::Warning => ("!", theme.warning),
+            Severity::Error => ("✗", theme.error),
+        };
+        let mut line = format!("{} {}", icon, notification.message.replace('\n', " "));
+        let others = state.notifications().len() - 1;
+        if others > 0 {
+            line.push_str(&format!(" (+{} more)", others));
+        }
+        let text = style.apply(theme.content(&Self::get_truncated_text(&line, width)));
+        print_text_with_coordinates(text, x, y, None, None);
     }
 
     /// Render new session creation screen
@@ -84,19 +222,16 @@ impl PluginRenderer {
         y: usize,
         width: usize,
         height: usize,
-    ) {
-        let colors = state
-            .colors()
-            .map(Colors::new)
-            .unwrap_or_else(|| Colors::new(Palette::default()));
+    ) -> Vec<HitRegion> {
         crate::ui::components::render_new_session_block(
             state.new_session_info(),
-            colors,
+            state.new_session_name_issue().as_ref(),
+            state.theme(),
             height.saturating_sub(2),
             width,
             x,
             y,
-        );
+        )
     }
 
     /// Render search results table
@@ -104,9 +239,10 @@ impl PluginRenderer {
         state: &PluginState,
         table_rows: usize,
         table_width: usize,
-        theme: &Option<Theme>,
+        theme: &Theme,
     ) -> Table {
-        let mut table = Table::new().add_row(vec!["Directory/Session"]);
+        let format = state.config().row_format.as_ref();
+        let mut table = Self::table_with_header(format);
         let results = state.search_engine().results();
         let selected_index = state.search_engine().selected_index();
 
@@ -116,15 +252,28 @@ impl PluginRenderer {
         for i in first_row..last_row {
             if let Some(result) = results.get(i) {
                 let is_selected = Some(i) == selected_index;
-                let mut table_cells = vec![Self::render_search_result_item(
-                    &result.item,
-                    &result.indices,
-                    table_width.saturating_sub(4),
-                    theme,
-                )];
+                let max_width = table_width.saturating_sub(4);
+                let mut table_cells = match format {
+                    Some(format) => Self::render_formatted_item(
+                        format,
+                        state,
+                        &result.item,
+                        &result.indices,
+                        "",
+                        max_width,
+                        theme,
+                    ),
+                    None => vec![Self::render_item(
+                        &result.item,
+                        &result.indices,
+                        "",
+                        max_width,
+                        theme,
+                    )],
+                };
 
                 if is_selected {
-                    table_cells = table_cells.drain(..).map(|t| t.selected()).collect();
+                    table_cells = table_cells.drain(..).map(|t| theme.select(t)).collect();
                 }
 
                 table = table.add_styled_row(table_cells);
@@ -139,26 +288,56 @@ impl PluginRenderer {
         state: &PluginState,
         table_rows: usize,
         table_width: usize,
-        theme: &Option<Theme>,
+        theme: &Theme,
     ) -> Table {
-        let mut table = Table::new().add_row(vec!["Directory/Session"]);
-        let items = state.display_items();
+        let format = state.config().row_format.as_ref();
+        let mut table = Self::table_with_header(format);
+        let rows = state.list_rows();
         let selected_index = state.selected_index();
+        let is_tree_view = state.is_tree_view();
 
         let (first_row, last_row) =
-            Self::calculate_render_range(table_rows, items.len(), selected_index);
+            Self::calculate_render_range(table_rows, rows.len(), selected_index);
 
         for i in first_row..last_row {
-            if let Some(item) = items.get(i) {
+            if let Some(row) = rows.get(i) {
                 let is_selected = Some(i) == selected_index;
-                let mut table_cells = vec![Self::render_item(
-                    item,
-                    table_width.saturating_sub(4),
-                    theme,
-                )];
+                let indent = if is_tree_view || row.group.is_some() {
+                    Self::tree_indent(row)
+                } else {
+                    "  ".repeat(row.depth)
+                };
+                let max_width = table_width.saturating_sub(4);
+                let mut table_cells = match (&row.kind, format) {
+                    (TreeRowKind::Item(item), Some(format)) => Self::render_formatted_item(
+                        format,
+                        state,
+                        item,
+                        &[],
+                        &indent,
+                        max_width,
+                        theme,
+                    ),
+                    (TreeRowKind::Item(item), None) => {
+                        vec![Self::render_item(item, &[], &indent, max_width, theme)]
+                    }
+                    (kind, Some(format)) => Self::render_formatted_group(
+                        format,
+                        &Self::header_label(kind),
+                        &indent,
+                        max_width,
+                        theme,
+                    ),
+                    (kind, None) => vec![Self::render_group(
+                        &Self::header_label(kind),
+                        &indent,
+                        max_width,
+                        theme,
+                    )],
+                };
 
                 if is_selected {
-                    table_cells = table_cells.drain(..).map(|t| t.selected()).collect();
+                    table_cells = table_cells.drain(..).map(|t| theme.select(t)).collect();
                 }
 
                 table = table.add_styled_row(table_cells);
@@ -168,189 +347,357 @@ impl PluginRenderer {
         table
     }
 
-    /// Render a search result item
-    fn render_search_result_item(
+    /// Create the list table with its header row
+    fn table_with_header(format: Option<&RowFormat>) -> Table {
+        match format {
+            Some(format) => Table::new().add_row(format.headers()),
+            None => Table::new().add_row(vec!["Directory/Session"]),
+        }
+    }
+
+    /// Values of an item for the configured row format. Search match indices refer to the
+    /// search text of the item (see `SearchEngine`) and are split into name and path highlights.
+    fn row_values(state: &PluginState, item: &SessionItem, indices: &[usize]) -> RowValues {
+        let field_indices = |start: usize, len: usize| -> Vec<usize> {
+            indices
+                .iter()
+                .filter(|&&i| i >= start && i < start + len)
+                .map(|i| i - start)
+                .collect()
+        };
+        let icon = state.theme().icons.for_item(item).to_string();
+
+        match item {
+            SessionItem::ExistingSession {
+                name, directory, ..
+            } => {
+                // Search text is "● name (directory)"
+                let path_start = name.chars().count() + 4;
+                RowValues {
+                    icon,
+                    name: name.clone(),
+                    path: directory.clone(),
+                    branch: state.git_branch(directory).unwrap_or_default().to_string(),
+                    age: String::new(),
+                    name_indices: field_indices(2, name.chars().count()),
+                    path_indices: field_indices(path_start, directory.chars().count()),
+                }
+            }
+            SessionItem::ResurrectableSession {
+                name,
+                directory,
+                duration,
+            } => RowValues {
+                icon,
+                name: name.clone(),
+                path: directory.clone(),
+                branch: state.git_branch(directory).unwrap_or_default().to_string(),
+                age: humantime::format_duration(*duration).to_string(),
+                name_indices: field_indices(2, name.chars().count()),
+                path_indices: Vec::new(),
+            },
+            SessionItem::Directory { path, session_name } => RowValues {
+                icon,
+                name: session_name.clone(),
+                path: path.clone(),
+                branch: state.git_branch(path).unwrap_or_default().to_string(),
+                age: String::new(),
+                name_indices: Vec::new(),
+                path_indices: indices.to_vec(),
+            },
+        }
+    }
+
+    /// Render the cells of an item with the configured row format
+    fn render_formatted_item(
+        format: &RowFormat,
+        state: &PluginState,
         item: &SessionItem,
         indices: &[usize],
+        indent: &str,
         max_width: usize,
-        theme: &Option<Theme>,
-    ) -> Text {
-        let mut text = Self::render_item(item, max_width, theme);
-
-        // Apply search highlighting
-        if !indices.is_empty() {
-            // Now indices should match the display text exactly since search matches against display text
-            // But we need to handle truncation for directories
-            let adjusted_indices = match item {
-                SessionItem::ExistingSession { .. } => {
-                    // Indices should match the display text exactly
-                    indices.to_vec()
-                }
-                SessionItem::ResurrectableSession { .. } => {
-                    // Indices should match the display text exactly
-                    indices.to_vec()
-                }
-                SessionItem::Directory { path, .. } => {
-                    // Handle truncation for long paths
-                    if path.len() > max_width && max_width > 10 {
-                        // Path is truncated with "..."
-                        let truncated_start = path.len().saturating_sub(max_width - 3);
-                        indices
-                            .iter()
-                            .filter_map(|&idx| {
-                                if idx >= truncated_start {
-                                    Some(idx - truncated_start + 3) // +3 for "..."
-                                } else {
-                                    None // Index is in truncated part
-                                }
-                            })
-                            .collect()
-                    } else {
-                        indices.to_vec()
-                    }
-                }
-            };
+        theme: &Theme,
+    ) -> Vec<Text> {
+        let values = Self::row_values(state, item, indices);
+        let mut cells = format.render(&values, max_width.saturating_sub(display_width(indent)));
+        if let Some(first) = cells.first_mut() {
+            let indent_len = indent.chars().count();
+            first.text.insert_str(0, indent);
+            first.indices.iter_mut().for_each(|i| *i += indent_len);
+        }
 
-            if !adjusted_indices.is_empty() {
-                if let Some(theme) = theme {
-                    text = theme.highlight(text, adjusted_indices);
-                } else {
-                    text = text.color_indices(3, adjusted_indices);
-                }
+        cells
+            .into_iter()
+            .map(|cell| {
+                let text = Self::item_text(item, &cell.text, theme);
+                theme.highlight(text, cell.indices)
+            })
+            .collect()
+    }
+
+    /// Render the cells of a group header with the configured row format
+    fn render_formatted_group(
+        format: &RowFormat,
+        label: &str,
+        indent: &str,
+        max_width: usize,
+        theme: &Theme,
+    ) -> Vec<Text> {
+        let mut cells = vec![String::new(); format.column_count()];
+        cells[format.label_column()] =
+            Self::get_truncated_text(label, max_width.saturating_sub(display_width(indent)));
+        cells[0].insert_str(0, indent);
+
+        cells
+            .iter()
+            .map(|cell| theme.group.apply(theme.content(cell)))
+            .collect()
+    }
+
+    /// Style text shown for an item according to its kind
+    fn item_text(item: &SessionItem, text: &str, theme: &Theme) -> Text {
+        let style = match item {
+            SessionItem::ExistingSession {
+                is_current: true, ..
+            } => theme.current_session,
+            SessionItem::ExistingSession { .. } => theme.available_session,
+            SessionItem::ResurrectableSession { .. } => theme.resurrectable,
+            SessionItem::Directory { .. } => theme.directory,
+        };
+        style.apply(theme.content(text))
+    }
+
+    /// Indentation and expand/collapse marker for a row of the tree view
+    fn tree_indent(row: &TreeRow) -> String {
+        let marker = match row.expanded {
+            Some(true) => "▾ ",
+            Some(false) => "▸ ",
+            None => "  ",
+        };
+        format!("{}{}", "  ".repeat(row.depth), marker)
+    }
+
+    /// Label of a group header or section heading, with the number of rows under it
+    fn header_label(kind: &TreeRowKind) -> String {
+        match kind {
+            TreeRowKind::Group { path, child_count } => {
+                format!("{}/ ({})", path.trim_end_matches('/'), child_count)
             }
+            TreeRowKind::Section { title, child_count } => format!("{} ({})", title, child_count),
+            TreeRowKind::Item(_) => String::new(),
         }
+    }
 
-        text
+    /// Render a group header of the tree view or a section heading
+    fn render_group(label: &str, indent: &str, max_width: usize, theme: &Theme) -> Text {
+        let truncated_text =
+            Self::get_truncated_text(label, max_width.saturating_sub(display_width(indent)));
+        let text = format!("{}{}", indent, truncated_text);
+        theme.group.apply(theme.content(&text))
     }
 
-    /// Render a session item
-    fn render_item(item: &SessionItem, max_width: usize, theme: &Option<Theme>) -> Text {
-        match item {
+    /// Render a session item, prefixed by the indentation of its row and its icon, with the
+    /// search match indices highlighted
+    fn render_item(
+        item: &SessionItem,
+        indices: &[usize],
+        indent: &str,
+        max_width: usize,
+        theme: &Theme,
+    ) -> Text {
+        let icon = theme.icons.for_item(item);
+        let prefix = if icon.is_empty() {
+            indent.to_string()
+        } else {
+            format!("{}{} ", indent, icon)
+        };
+        let max_width = max_width.saturating_sub(display_width(&prefix));
+        let truncated = match item {
             SessionItem::ExistingSession {
-                name,
-                directory,
-                is_current,
+                name, directory, ..
             } => {
-                let prefix = if *is_current { "● " } else { "○ " };
-                let display_text = format!("{}{} ({})", prefix, name, directory);
-
-                let truncated_text = Self::get_truncated_text(&display_text, max_width);
-
-                if let Some(theme) = theme {
-                    if *is_current {
-                        theme.current_session(&truncated_text)
-                    } else {
-                        theme.available_session(&truncated_text)
-                    }
-                } else {
-                    let mut text = Text::new(&truncated_text);
-                    if *is_current {
-                        text = text.color_range(2, ..);
-                    } else {
-                        text = text.color_range(3, ..);
-                    }
-                    text
-                }
+                let display_text = format!("{} ({})", name, directory);
+                truncate_middle(&display_text, &Self::skip_search_icon(indices), max_width)
             }
-            SessionItem::ResurrectableSession { name, duration } => {
+            SessionItem::ResurrectableSession { name, duration, .. } => {
                 let display_text = format!(
-                    "↺ {} (created {} ago)",
+                    "{} (created {} ago)",
                     name,
                     humantime::format_duration(*duration)
                 );
-
-                let truncated_text = Self::get_truncated_text(&display_text, max_width);
-
-                if let Some(theme) = theme {
-                    theme.available_session(&truncated_text)
-                } else {
-                    Text::new(&truncated_text).color_range(4, ..)
-                }
+                truncate_middle(&display_text, &Self::skip_search_icon(indices), max_width)
             }
-            SessionItem::Directory { path, .. } => {
-                let display_path = if path.len() > max_width && max_width > 10 {
-                    format!("...{}", &path[path.len().saturating_sub(max_width - 3)..])
-                } else {
-                    path.to_string()
-                };
+            SessionItem::Directory { path, .. } => truncate_path(path, indices, max_width),
+        };
 
-                if let Some(theme) = theme {
-                    theme.content(&display_path)
-                } else {
-                    Text::new(&display_path)
-                }
-            }
-        }
+        let prefix_len = prefix.chars().count();
+        let text = Self::item_text(item, &format!("{}{}", prefix, truncated.text), theme);
+        let indices = truncated.indices.iter().map(|i| i + prefix_len).collect();
+        theme.highlight(text, indices)
+    }
+
+    /// Make match indices of a session relative to the text after its icon. Sessions are
+    /// searched with the default icon and a space in front (see `SearchEngine`), which the
+    /// configured icon replaces when rendering.
+    fn skip_search_icon(indices: &[usize]) -> Vec<usize> {
+        indices.iter().filter(|&&i| i >= 2).map(|i| i - 2).collect()
     }
 
     /// Render help text
-    fn render_help_text(state: &PluginState, x: usize, y: usize, theme: &Option<Theme>) {
+    fn render_help_text(state: &PluginState, x: usize, y: usize, theme: &Theme) {
+        if let Some(mode) = state.vim_mode() {
+            Self::render_vim_help_text(state, mode, x, y, theme);
+            return;
+        }
         let help_text = if state.display_items().is_empty() {
             "Type session name and press Enter • Ctrl+Enter: Quick create • Esc: Exit"
+        } else if state.is_tree_view() {
+            "↑/↓: Navigate • PgUp/PgDn/Home/End: Jump • ←/→: Collapse/Expand • Enter: Switch/New • Ctrl+Enter: Quick create • Tab: Actions • Ctrl+p/n: History • Ctrl+e: Search mode • Ctrl+r: reload directories • Ctrl+x: Resurrectable • Delete: Kill • Type: Search • Esc: Exit"
         } else {
-            "↑/↓: Navigate • Enter: Switch/New • Ctrl+Enter: Quick create • Ctrl+r: reload directories • Delete: Kill • Type: Search • Esc: Exit"
+            "↑/↓: Navigate • PgUp/PgDn/Home/End: Jump • Enter: Switch/New • Ctrl+Enter: Quick create • Tab: Actions • Ctrl+p/n: History • Ctrl+e: Search mode • Ctrl+r: reload directories • Ctrl+x: Resurrectable • Delete: Kill • Type: Search • Esc: Exit"
         };
 
-        let text = if let Some(theme) = theme {
-            theme.content(help_text).color_range(1, ..)
+        print_text_with_coordinates(theme.hint(help_text), x, y, None, None);
+    }
+
+    /// Render help text for vim mode, led by the current mode
+    fn render_vim_help_text(state: &PluginState, mode: VimMode, x: usize, y: usize, theme: &Theme) {
+        let help_text = match mode {
+            VimMode::Normal if state.is_tree_view() => {
+                "j/k: Navigate • gg/G: First/Last • ←/→: Collapse/Expand • /: Search • Enter: Switch/New • Tab: Actions • dd: Kill • r: Rename • p: Pin • Esc: Exit"
+            }
+            VimMode::Normal => {
+                "j/k: Navigate • gg/G: First/Last • /: Search • Enter: Switch/New • Tab: Actions • dd: Kill • r: Rename • p: Pin • Esc: Clear search/Exit"
+            }
+            VimMode::Insert => {
+                "Type: Search • ↑/↓: Navigate • Enter: Switch/New • Ctrl+Enter: Quick create • Esc: Normal mode"
+            }
+        };
+        let label = mode.label();
+        let text = theme.hint(&format!("{} • {}", label, help_text));
+        let text = theme.key.apply_range(text, ..label.len());
+        print_text_with_coordinates(text, x, y, None, None);
+    }
+
+    /// Dialog shown over the current screen, if any
+    pub fn overlay(state: &PluginState) -> Option<Modal> {
+        let theme = state.theme();
+        let modal = if let Some(error) = state.error() {
+            Some(Self::error_dialog(error, theme))
+        } else if let Some(session_name) = state.session_manager().pending_deletion() {
+            Some(Self::deletion_confirmation(session_name, theme))
+        } else if !state.session_manager().pending_purge().is_empty() {
+            Some(Self::purge_confirmation(state, theme))
+        } else if let Some(menu) = state.action_menu() {
+            Some(Self::action_menu(menu, theme))
+        } else if let Some(input) = state.rename_input() {
+            Some(Self::rename_prompt(input, theme))
+        } else if let Some((path, input)) = state.alias_input() {
+            Some(Self::alias_prompt(path, input, theme))
         } else {
-            Text::new(help_text).color_range(1, ..)
+            state
+                .purge_input()
+                .map(|input| Self::purge_prompt(input, theme))
         };
+        modal.map(|modal| modal.scroll(state.modal_scroll()))
+    }
 
-        print_text_with_coordinates(text, x, y, None, None);
+    /// Dialog showing an error message
+    fn error_dialog(error: &str, theme: &Theme) -> Modal {
+        Modal::new("Error", theme.error)
+            .paragraph(error, theme.error)
+            .button("Enter/Esc", "Close", BareKey::Esc)
     }
 
-    /// Render error message
-    fn render_error(error: &str, x: usize, y: usize, _width: usize, height: usize) {
-        let dialog_y = y + height / 2;
-        let error_text = Text::new(error).color_range(1, ..);
-        print_text_with_coordinates(error_text, x, dialog_y, None, None);
+    /// Dialog asking to confirm killing a session
+    fn deletion_confirmation(session_name: &str, theme: &Theme) -> Modal {
+        Modal::new("Kill session", theme.warning)
+            .paragraph(&format!("Kill session '{}'?", session_name), Style::PLAIN)
+            .blank()
+            .paragraph(
+                "If this is a resurrectable session, it will be deleted. This action cannot be undone.",
+                theme.warning,
+            )
+            .button("y", "Confirm", BareKey::Char('y'))
+            .button("n/Esc", "Cancel", BareKey::Esc)
     }
 
-    /// Render deletion confirmation dialog
-    fn render_deletion_confirmation(
-        session_name: &str,
-        x: usize,
-        y: usize,
-        width: usize,
-        height: usize,
-        _colors: Option<Palette>,
-    ) {
-        let dialog_width = std::cmp::min(60, width.saturating_sub(4));
-        let dialog_height = 6;
-        let dialog_x = x + (width.saturating_sub(dialog_width)) / 2;
-        let dialog_y = y + (height.saturating_sub(dialog_height)) / 2;
-
-        let message = format!("Kill session '{}'?", session_name);
-        let warning =
-            "If this is a resurrectable session, it will be deleted. This action cannot be undone.";
-        let prompt = "Press 'y' to confirm, 'n' or Esc to cancel";
-
-        let dialog_lines = vec![
-            "┌".to_string() + &"─".repeat(dialog_width.saturating_sub(2)) + "┐",
-            format!(
-                "│{:^width$}│",
-                message,
-                width = dialog_width.saturating_sub(2)
-            ),
-            format!(
-                "│{:^width$}│",
-                warning,
-                width = dialog_width.saturating_sub(2)
-            ),
-            format!("│{:^width$}│", "", width = dialog_width.saturating_sub(2)),
-            format!(
-                "│{:^width$}│",
-                prompt,
-                width = dialog_width.saturating_sub(2)
-            ),
-            "└".to_string() + &"─".repeat(dialog_width.saturating_sub(2)) + "┘",
-        ];
+    /// Dialog of the action menu for the selected item
+    fn action_menu(menu: &ActionMenu, theme: &Theme) -> Modal {
+        let title = match menu.item() {
+            SessionItem::ExistingSession { name, .. }
+            | SessionItem::ResurrectableSession { name, .. } => name.as_str(),
+            SessionItem::Directory { path, .. } => path.as_str(),
+        };
+        let mut modal = Modal::new(title, theme.title)
+            .input("Filter:", menu.input())
+            .blank()
+            // Keep the dialog the same size while filtering so it doesn't jump around
+            .min_body_height(menu.actions().len() + 2);
+        for (i, (action, indices)) in menu.results().iter().enumerate() {
+            modal = modal.line(
+                action.label(),
+                Style::PLAIN,
+                indices.clone(),
+                i == menu.selected_index(),
+            );
+        }
+        modal
+            .hint("↑/↓", "Select")
+            .button("Enter", "Run", BareKey::Enter)
+            .hint("Type", "Filter")
+            .button("Esc", "Cancel", BareKey::Esc)
+    }
+
+    /// Dialog for renaming the current session
+    fn rename_prompt(input: &TextInput, theme: &Theme) -> Modal {
+        Modal::new("Rename session", theme.title)
+            .input("New name:", input)
+            .button("Enter", "Rename", BareKey::Enter)
+            .button("Esc", "Cancel", BareKey::Esc)
+    }
+
+    /// Dialog for setting the session name alias of a directory
+    fn alias_prompt(path: &str, input: &TextInput, theme: &Theme) -> Modal {
+        Modal::new("Session name alias", theme.title)
+            .paragraph(path, theme.directory)
+            .paragraph("Leave empty to remove the alias.", theme.hint)
+            .blank()
+            .input("Alias:", input)
+            .button("Enter", "Save", BareKey::Enter)
+            .button("Esc", "Cancel", BareKey::Esc)
+    }
+
+    /// Dialog asking for the age of resurrectable sessions to purge
+    fn purge_prompt(input: &TextInput, theme: &Theme) -> Modal {
+        Modal::new("Purge old sessions", theme.title)
+            .paragraph(
+                "Delete every resurrectable session that exited more than this many days ago.",
+                Style::PLAIN,
+            )
+            .blank()
+            .input("Days:", input)
+            .button("Enter", "Continue", BareKey::Enter)
+            .button("Esc", "Cancel", BareKey::Esc)
+    }
 
-        for (i, line) in dialog_lines.iter().enumerate() {
-            let text = Text::new(line).color_range(1, ..);
-            print_text_with_coordinates(text, dialog_x, dialog_y + i, None, None);
+    /// Dialog listing the resurrectable sessions about to be purged
+    fn purge_confirmation(state: &PluginState, theme: &Theme) -> Modal {
+        let session_names = state.session_manager().pending_purge();
+        let mut modal = Modal::new("Purge old sessions", theme.warning)
+            .paragraph(
+                &format!("Delete {} resurrectable sessions?", session_names.len()),
+                Style::PLAIN,
+            )
+            .blank();
+        for name in session_names {
+            modal = modal.paragraph(name, theme.resurrectable);
         }
+        modal
+            .blank()
+            .paragraph("This action cannot be undone.", theme.warning)
+            .button("y", "Confirm", BareKey::Char('y'))
+            .button("n/Esc", "Cancel", BareKey::Esc)
     }
 
     /// Calculate main UI size
@@ -381,14 +728,6 @@ impl PluginRenderer {
     }
 
     fn get_truncated_text(text: &str, max_width: usize) -> String {
-        if text.len() > max_width && max_width > 10 {
-            format!(
-                "{}...{}",
-                &text[..10],
-                &text[text.len().saturating_sub(max_width - 13)..]
-            )
-        } else {
-            text.to_string()
-        }
+        truncate_middle(text, &[], max_width).text
     }
 }
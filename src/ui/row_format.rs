@@ -0,0 +1,242 @@
+use crate::ui::truncate::{self, display_width, Truncated};
+
+/// A value of an item that can be shown in a row
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Field {
+    /// Session state indicator
+    Icon,
+    /// Session name, or the generated session name of a directory
+    Name,
+    /// Directory path
+    Path,
+    /// Current git branch of the directory
+    Branch,
+    /// Time since a resurrectable session was created
+    Age,
+}
+
+impl Field {
+    /// Look up a field by its placeholder name
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "icon" => Some(Field::Icon),
+            "name" => Some(Field::Name),
+            "path" => Some(Field::Path),
+            "branch" => Some(Field::Branch),
+            "age" => Some(Field::Age),
+            _ => None,
+        }
+    }
+
+    /// Column title shown in the table header
+    fn title(&self) -> &'static str {
+        match self {
+            Field::Icon => "",
+            Field::Name => "Name",
+            Field::Path => "Path",
+            Field::Branch => "Branch",
+            Field::Age => "Age",
+        }
+    }
+}
+
+/// Part of a column template
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    /// Text shown as is
+    Literal(String),
+    /// Placeholder replaced by a value of the item
+    Field(Field),
+}
+
+/// A column of the row format
+#[derive(Debug, Clone, PartialEq)]
+struct Column {
+    /// Literal text and placeholders making up the column
+    segments: Vec<Segment>,
+    /// Maximum display width, if set with `{field:width}`
+    width: Option<usize>,
+}
+
+impl Column {
+    /// Check if the column shows a field
+    fn uses(&self, field: Field) -> bool {
+        self.segments.contains(&Segment::Field(field))
+    }
+
+    /// Width of the column if it doesn't share the free space: its configured width,
+    /// or the full width of columns showing only literal text and the one-character icon
+    fn fixed_width(&self) -> Option<usize> {
+        self.width.or_else(|| {
+            self.segments
+                .iter()
+                .map(|segment| match segment {
+                    Segment::Literal(text) => Some(display_width(text)),
+                    Segment::Field(Field::Icon) => Some(1),
+                    Segment::Field(_) => None,
+                })
+                .sum()
+        })
+    }
+}
+
+/// Values of an item for a row, with the character indices of `name` and `path` to highlight
+#[derive(Debug, Default)]
+pub struct RowValues {
+    pub icon: String,
+    pub name: String,
+    pub path: String,
+    pub branch: String,
+    pub age: String,
+    pub name_indices: Vec<usize>,
+    pub path_indices: Vec<usize>,
+}
+
+impl RowValues {
+    /// Get the value of a field along with the indices to highlight in it
+    fn get(&self, field: Field) -> (&str, &[usize]) {
+        match field {
+            Field::Icon => (&self.icon, &[]),
+            Field::Name => (&self.name, &self.name_indices),
+            Field::Path => (&self.path, &self.path_indices),
+            Field::Branch => (&self.branch, &[]),
+            Field::Age => (&self.age, &[]),
+        }
+    }
+}
+
+/// Text of a single table cell, with the character indices to highlight
+#[derive(Debug, Default)]
+pub struct Cell {
+    pub text: String,
+    pub indices: Vec<usize>,
+}
+
+/// Template for the columns of list rows, e.g. `{icon} {name:20} {path} {branch}`.
+/// Whitespace separates columns; text around placeholders is kept within its column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowFormat {
+    columns: Vec<Column>,
+}
+
+impl RowFormat {
+    /// Parse a row format template, rejecting unknown placeholders
+    pub fn from_config(value: &str) -> Option<Self> {
+        let columns = value
+            .split_whitespace()
+            .map(Self::parse_column)
+            .collect::<Option<Vec<Column>>>()?;
+        if columns.is_empty() {
+            return None;
+        }
+        Some(Self { columns })
+    }
+
+    /// Parse a single whitespace-free column template
+    fn parse_column(template: &str) -> Option<Column> {
+        let mut segments = Vec::new();
+        let mut width = None;
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                segments.push(Segment::Literal(rest[..start].to_string()));
+            }
+            let end = start + rest[start..].find('}')?;
+            let placeholder = &rest[start + 1..end];
+            let (name, field_width) = match placeholder.split_once(':') {
+                Some((name, field_width)) => (name, Some(field_width.parse::<usize>().ok()?)),
+                None => (placeholder, None),
+            };
+            segments.push(Segment::Field(Field::from_name(name)?));
+            width = width.or(field_width);
+            rest = &rest[end + 1..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_string()));
+        }
+        Some(Column { segments, width })
+    }
+
+    /// Check if any column shows a field
+    pub fn uses(&self, field: Field) -> bool {
+        self.columns.iter().any(|column| column.uses(field))
+    }
+
+    /// Titles of the columns for the table header
+    pub fn headers(&self) -> Vec<String> {
+        self.columns
+            .iter()
+            .map(|column| {
+                column
+                    .segments
+                    .iter()
+                    .filter_map(|segment| match segment {
+                        Segment::Field(field) => Some(field.title()),
+                        Segment::Literal(_) => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect()
+    }
+
+    /// Index of the column group headers are shown in: the path column, else the name column
+    pub fn label_column(&self) -> usize {
+        [Field::Path, Field::Name]
+            .iter()
+            .find_map(|field| self.columns.iter().position(|column| column.uses(*field)))
+            .unwrap_or(self.columns.len().saturating_sub(1))
+    }
+
+    /// Number of columns
+    pub fn column_count(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Render the cells of a row within `max_width` columns. Columns without a width share
+    /// the space left by the others; path columns drop segments from the middle of the path
+    /// so its start and end stay visible.
+    pub fn render(&self, values: &RowValues, max_width: usize) -> Vec<Cell> {
+        let column_widths = self.column_widths(max_width);
+        self.columns
+            .iter()
+            .zip(column_widths)
+            .map(|(column, width)| {
+                let mut cell = Cell::default();
+                for segment in &column.segments {
+                    match segment {
+                        Segment::Literal(text) => cell.text.push_str(text),
+                        Segment::Field(field) => {
+                            let (value, indices) = values.get(*field);
+                            let offset = cell.text.chars().count();
+                            cell.indices.extend(indices.iter().map(|i| i + offset));
+                            cell.text.push_str(value);
+                        }
+                    }
+                }
+                let Truncated { text, indices } = if column.uses(Field::Path) {
+                    truncate::truncate_path(&cell.text, &cell.indices, width)
+                } else {
+                    truncate::truncate_end(&cell.text, &cell.indices, width)
+                };
+                Cell { text, indices }
+            })
+            .collect()
+    }
+
+    /// Width available to each column, with one space separating columns
+    fn column_widths(&self, max_width: usize) -> Vec<usize> {
+        let fixed: usize = self.columns.iter().filter_map(Column::fixed_width).sum();
+        let flexible = self
+            .columns
+            .iter()
+            .filter(|column| column.fixed_width().is_none())
+            .count();
+        let separators = self.columns.len().saturating_sub(1);
+        let shared = max_width.saturating_sub(fixed + separators) / flexible.max(1);
+        self.columns
+            .iter()
+            .map(|column| column.fixed_width().unwrap_or(shared))
+            .collect()
+    }
+}
@@ -1,25 +1,267 @@
-use zellij_tile::prelude::{Palette, Text};
+use std::collections::BTreeMap;
+use std::ops::RangeBounds;
 
-/// Color theme for the plugin UI
-/// Uses indexed colors that map to user's Zellij theme:
+use zellij_tile::prelude::Text;
+
+use crate::session::SessionItem;
+
+/// How a piece of text is drawn. Colors are the emphasis colors of the user's Zellij theme,
+/// which Zellij resolves from its palette when rendering:
 /// 0 = dim/subtle, 1 = warning/orange, 2 = success/green, 3 = info/blue
-#[derive(Copy, Clone, Debug)]
-pub struct Theme;
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Style {
+    /// Emphasis color index, or `None` for the default text color
+    color: Option<usize>,
+    /// Whether the text is drawn with the selection background
+    selected: bool,
+}
+
+impl Style {
+    /// Default text color
+    pub const PLAIN: Style = Style {
+        color: None,
+        selected: false,
+    };
+
+    /// Text in one of the emphasis colors
+    pub const fn color(index: usize) -> Self {
+        Style {
+            color: Some(index),
+            selected: false,
+        }
+    }
+
+    /// Parse a style from config: space separated `plain`, `selected` and an emphasis color
+    /// given as `emphasis_0` to `emphasis_3` or just its index, e.g. `selected emphasis_2`
+    pub fn from_config(value: &str) -> Option<Self> {
+        let mut style = Style::PLAIN;
+        for word in value.split_whitespace() {
+            match word {
+                "plain" => style.color = None,
+                "selected" => style.selected = true,
+                _ => {
+                    let index = word.strip_prefix("emphasis_").unwrap_or(word);
+                    style.color = Some(index.parse::<usize>().ok().filter(|i| *i <= 3)?);
+                }
+            }
+        }
+        Some(style)
+    }
+
+    /// Apply the style to the whole text
+    pub fn apply(&self, text: Text) -> Text {
+        let text = match self.color {
+            Some(color) => text.color_range(color, ..),
+            None => text,
+        };
+        if self.selected {
+            text.selected()
+        } else {
+            text
+        }
+    }
+
+    /// Color a range of characters of the text. The selection background always covers the
+    /// whole text, so it's left out here.
+    pub fn apply_range<R: RangeBounds<usize>>(&self, text: Text, range: R) -> Text {
+        match self.color {
+            Some(color) => text.color_range(color, range),
+            None => text,
+        }
+    }
+
+    /// Color single characters of the text
+    pub fn apply_indices(&self, text: Text, indices: Vec<usize>) -> Text {
+        match self.color {
+            Some(color) if !indices.is_empty() => text.color_indices(color, indices),
+            _ => text,
+        }
+    }
+}
+
+/// Icons marking the kind of a list item
+#[derive(Clone, Debug, PartialEq)]
+pub struct Icons {
+    pub current_session: String,
+    pub session: String,
+    pub resurrectable: String,
+    pub directory: String,
+}
+
+impl Icons {
+    fn new(current_session: &str, session: &str, resurrectable: &str, directory: &str) -> Self {
+        Self {
+            current_session: current_session.to_string(),
+            session: session.to_string(),
+            resurrectable: resurrectable.to_string(),
+            directory: directory.to_string(),
+        }
+    }
+
+    /// Look up a built-in icon set: `unicode` (default), `nerd` for Nerd Fonts, or `ascii`
+    fn preset(name: &str) -> Option<Self> {
+        match name {
+            "unicode" => Some(Self::default()),
+            "nerd" => Some(Self::new("\u{f111}", "\u{f10c}", "\u{f0e2}", "\u{f07b}")),
+            "ascii" => Some(Self::new("*", "o", "~", "")),
+            _ => None,
+        }
+    }
+
+    /// Icon of an item, empty if it has none
+    pub fn for_item(&self, item: &SessionItem) -> &str {
+        match item {
+            SessionItem::ExistingSession {
+                is_current: true, ..
+            } => &self.current_session,
+            SessionItem::ExistingSession { .. } => &self.session,
+            SessionItem::ResurrectableSession { .. } => &self.resurrectable,
+            SessionItem::Directory { .. } => &self.directory,
+        }
+    }
+}
+
+impl Default for Icons {
+    fn default() -> Self {
+        Self::new("●", "○", "↺", "")
+    }
+}
+
+/// Color theme for the plugin UI, mapping each semantic role to a style
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    /// Plugin title
+    pub title: Style,
+    /// The session this plugin runs in
+    pub current_session: Style,
+    /// Other running sessions
+    pub available_session: Style,
+    /// Exited sessions that can be resurrected
+    pub resurrectable: Style,
+    /// Zoxide directories
+    pub directory: Style,
+    /// Group headers of the tree view
+    pub group: Style,
+    /// Characters matching the search
+    pub highlight: Style,
+    /// Cursor of text inputs
+    pub cursor: Style,
+    /// Selected row
+    pub selection: Style,
+    /// Input prompts and labels
+    pub prompt: Style,
+    /// Text typed into inputs
+    pub input: Style,
+    /// Key names within prompts, e.g. `<ENTER>`
+    pub key: Style,
+    /// Key hints
+    pub hint: Style,
+    /// Warnings
+    pub warning: Style,
+    /// Errors
+    pub error: Style,
+    /// Success messages
+    pub success: Style,
+    /// Item icons
+    pub icons: Icons,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            title: Style::color(3),
+            current_session: Style::color(2),
+            available_session: Style::color(3),
+            resurrectable: Style::color(3),
+            directory: Style::PLAIN,
+            group: Style::color(2),
+            highlight: Style::color(3),
+            cursor: Style::color(3),
+            selection: Style {
+                color: None,
+                selected: true,
+            },
+            prompt: Style::color(2),
+            input: Style::color(0),
+            key: Style::color(3),
+            hint: Style::color(1),
+            warning: Style::color(1),
+            error: Style::color(1),
+            success: Style::color(2),
+            icons: Icons::default(),
+        }
+    }
+}
 
 impl Theme {
-    /// Create theme from a palette (not used, colors are indexed)
-    pub fn new(_palette: Palette) -> Self {
-        Self
+    /// Create a theme from plugin configuration. Roles are overridden with `theme_<role>` keys,
+    /// icons with an `icons` preset and `icon_<kind>` keys; anything invalid keeps its default.
+    /// Also returns the `theme_<role>` keys whose style is invalid.
+    pub fn from_config(config: &BTreeMap<String, String>) -> (Self, Vec<&'static str>) {
+        let mut theme = Self::default();
+        let mut invalid_keys = Vec::new();
+        let styles = [
+            ("theme_title", &mut theme.title),
+            ("theme_current_session", &mut theme.current_session),
+            ("theme_available_session", &mut theme.available_session),
+            ("theme_resurrectable", &mut theme.resurrectable),
+            ("theme_directory", &mut theme.directory),
+            ("theme_group", &mut theme.group),
+            ("theme_highlight", &mut theme.highlight),
+            ("theme_cursor", &mut theme.cursor),
+            ("theme_selection", &mut theme.selection),
+            ("theme_prompt", &mut theme.prompt),
+            ("theme_input", &mut theme.input),
+            ("theme_key", &mut theme.key),
+            ("theme_hint", &mut theme.hint),
+            ("theme_warning", &mut theme.warning),
+            ("theme_error", &mut theme.error),
+            ("theme_success", &mut theme.success),
+        ];
+        for (key, style) in styles {
+            match config.get(key).map(|v| Style::from_config(v)) {
+                Some(Some(value)) => *style = value,
+                Some(None) => invalid_keys.push(key),
+                None => {}
+            }
+        }
+
+        if let Some(icons) = config.get("icons").and_then(|name| Icons::preset(name)) {
+            theme.icons = icons;
+        }
+        let icons = [
+            ("icon_current_session", &mut theme.icons.current_session),
+            ("icon_session", &mut theme.icons.session),
+            ("icon_resurrectable", &mut theme.icons.resurrectable),
+            ("icon_directory", &mut theme.icons.directory),
+        ];
+        for (key, icon) in icons {
+            if let Some(value) = config.get(key) {
+                *icon = value.trim().to_string();
+            }
+        }
+
+        (theme, invalid_keys)
     }
 
-    /// Text for warnings (color index 1 = orange/yellow)
+    /// Text for warnings
     pub fn warning(&self, text: &str) -> Text {
-        Text::new(text).color_range(1, ..)
+        self.warning.apply(Text::new(text))
+    }
+
+    /// Text for errors
+    pub fn error(&self, text: &str) -> Text {
+        self.error.apply(Text::new(text))
     }
 
-    /// Text for titles (color index 3 = blue/cyan)
+    /// Text for titles
     pub fn title(&self, text: &str) -> Text {
-        Text::new(text).color_range(3, ..)
+        self.title.apply(Text::new(text))
+    }
+
+    /// Text for key hints
+    pub fn hint(&self, text: &str) -> Text {
+        self.hint.apply(Text::new(text))
     }
 
     /// Text for regular content (default color)
@@ -27,18 +269,21 @@ impl Theme {
         Text::new(text)
     }
 
-    /// Text for current session (color index 2 = green - active)
-    pub fn current_session(&self, text: &str) -> Text {
-        Text::new(text).color_range(2, ..)
+    /// Highlight search matches at the given character indices
+    pub fn highlight(&self, text: Text, indices: Vec<usize>) -> Text {
+        self.highlight.apply_indices(text, indices)
     }
 
-    /// Text for available session (color index 3 = cyan - available)
-    pub fn available_session(&self, text: &str) -> Text {
-        Text::new(text).color_range(3, ..)
+    /// Highlight the cursor of a text input rendered `offset` characters into `text`
+    pub fn with_cursor(&self, text: Text, cursor: Option<usize>, offset: usize) -> Text {
+        match cursor {
+            Some(cursor) => self.cursor.apply_indices(text, vec![offset + cursor]),
+            None => text,
+        }
     }
 
-    /// Text for search highlights (color index 3 = yellow/blue)
-    pub fn highlight(&self, text: Text, indices: Vec<usize>) -> Text {
-        text.color_indices(3, indices)
+    /// Mark text as part of the selected row
+    pub fn select(&self, text: Text) -> Text {
+        self.selection.apply(text)
     }
 }
@@ -0,0 +1,456 @@
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Marker inserted where text was cut
+const ELLIPSIS: &str = "...";
+
+/// Text cut down to fit a display width, with highlight indices remapped to it.
+/// Indices are character indices, matching those taken by `Text::color_indices`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Truncated {
+    pub text: String,
+    pub indices: Vec<usize>,
+}
+
+/// Part of a truncated text
+enum Piece {
+    /// Characters kept from the original text
+    Kept(Range<usize>),
+    /// Text inserted in place of the characters that were cut
+    Inserted(&'static str),
+}
+
+/// Number of terminal columns a text takes up
+pub fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// Pad text with spaces up to a display width
+pub fn pad_to_width(text: &str, width: usize) -> String {
+    format!(
+        "{}{}",
+        text,
+        " ".repeat(width.saturating_sub(display_width(text)))
+    )
+}
+
+/// Keep the start of the text, ending it with "..." if it doesn't fit
+pub fn truncate_end(text: &str, indices: &[usize], max_width: usize) -> Truncated {
+    if display_width(text) <= max_width {
+        return unchanged(text, indices);
+    }
+    let (budget, ellipsis) = ellipsis_budget(max_width);
+    let clusters = clusters(text);
+    let end = prefix_end(&clusters, budget);
+    assemble(
+        text,
+        &[Piece::Kept(0..end), Piece::Inserted(ellipsis)],
+        indices,
+    )
+}
+
+/// Keep the end of the text, starting it with "..." if it doesn't fit
+pub fn truncate_start(text: &str, indices: &[usize], max_width: usize) -> Truncated {
+    if display_width(text) <= max_width {
+        return unchanged(text, indices);
+    }
+    let (budget, ellipsis) = ellipsis_budget(max_width);
+    let clusters = clusters(text);
+    let start = suffix_start(&clusters, budget);
+    let len = clusters.last().map(|(range, _)| range.end).unwrap_or(0);
+    assemble(
+        text,
+        &[Piece::Inserted(ellipsis), Piece::Kept(start..len)],
+        indices,
+    )
+}
+
+/// Keep the start and end of the text, joined by "..." if it doesn't fit
+pub fn truncate_middle(text: &str, indices: &[usize], max_width: usize) -> Truncated {
+    if display_width(text) <= max_width {
+        return unchanged(text, indices);
+    }
+    let (budget, ellipsis) = ellipsis_budget(max_width);
+    let clusters = clusters(text);
+    let len = clusters.last().map(|(range, _)| range.end).unwrap_or(0);
+    // Favour the end, which tends to be the more specific part
+    let head_end = prefix_end(&clusters, budget / 3);
+    let head_width = display_width(&char_slice(text, 0..head_end));
+    let tail_start = suffix_start(&clusters, budget - head_width).max(head_end);
+    assemble(
+        text,
+        &[
+            Piece::Kept(0..head_end),
+            Piece::Inserted(ellipsis),
+            Piece::Kept(tail_start..len),
+        ],
+        indices,
+    )
+}
+
+/// Shorten a path by replacing whole segments in its middle with "...", keeping its first
+/// segment and as many trailing segments as fit, e.g. `/home/.../project/src`. Falls back
+/// to `truncate_start` when even the first and last segments don't fit.
+pub fn truncate_path(path: &str, indices: &[usize], max_width: usize) -> Truncated {
+    if display_width(path) <= max_width {
+        return unchanged(path, indices);
+    }
+
+    // Character index of every separator
+    let separators: Vec<usize> = path
+        .chars()
+        .enumerate()
+        .filter(|(_, c)| *c == '/')
+        .map(|(i, _)| i)
+        .collect();
+    // The head runs up to the separator after the first non-empty segment
+    let head_end = separators.iter().copied().find(|&i| i > 0).unwrap_or(0);
+    let len = path.chars().count();
+    let marker = "/...";
+    let head_width = display_width(&char_slice(path, 0..head_end)) + display_width(marker);
+
+    // Add trailing segments while they fit, starting from the last one
+    let mut tail_start = None;
+    for &separator in separators.iter().rev() {
+        if separator <= head_end {
+            break;
+        }
+        let tail_width = display_width(&char_slice(path, separator..len));
+        if head_width + tail_width > max_width {
+            break;
+        }
+        tail_start = Some(separator);
+    }
+
+    match tail_start {
+        Some(tail_start) if head_end > 0 => assemble(
+            path,
+            &[
+                Piece::Kept(0..head_end),
+                Piece::Inserted(marker),
+                Piece::Kept(tail_start..len),
+            ],
+            indices,
+        ),
+        _ => truncate_start(path, indices, max_width),
+    }
+}
+
+/// Break text into lines of at most `max_width` columns at whitespace, keeping line breaks
+/// of the text. Words longer than a line are split between grapheme clusters.
+pub fn wrap(text: &str, max_width: usize) -> Vec<String> {
+    let max_width = max_width.max(1);
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let mut word = word.to_string();
+            loop {
+                let separator = usize::from(!line.is_empty());
+                if display_width(&line) + separator + display_width(&word) <= max_width {
+                    if !line.is_empty() {
+                        line.push(' ');
+                    }
+                    line.push_str(&word);
+                    break;
+                }
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                    continue;
+                }
+                // The word alone is too long, so fill the line with as much of it as fits,
+                // and at least one cluster in case that is wider than the line
+                let clusters = clusters(&word);
+                let end = match prefix_end(&clusters, max_width) {
+                    0 => clusters.first().map(|(range, _)| range.end).unwrap_or(1),
+                    end => end,
+                };
+                lines.push(char_slice(&word, 0..end));
+                word = word.chars().skip(end).collect();
+                if word.is_empty() {
+                    break;
+                }
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Text that already fits
+fn unchanged(text: &str, indices: &[usize]) -> Truncated {
+    Truncated {
+        text: text.to_string(),
+        indices: indices.to_vec(),
+    }
+}
+
+/// Width left for text next to the ellipsis, and the ellipsis that fits in `max_width`
+fn ellipsis_budget(max_width: usize) -> (usize, &'static str) {
+    let ellipsis = &ELLIPSIS[..max_width.min(ELLIPSIS.len())];
+    (max_width - ellipsis.len(), ellipsis)
+}
+
+/// Character range and display width of every grapheme cluster, so cuts never split
+/// a character from its combining marks or a wide character in half
+fn clusters(text: &str) -> Vec<(Range<usize>, usize)> {
+    let mut start = 0;
+    text.graphemes(true)
+        .map(|grapheme| {
+            let len = grapheme.chars().count();
+            let range = start..start + len;
+            start += len;
+            (range, display_width(grapheme))
+        })
+        .collect()
+}
+
+/// End of the longest run of clusters from the start that fits in `budget` columns
+fn prefix_end(clusters: &[(Range<usize>, usize)], budget: usize) -> usize {
+    let mut width = 0;
+    let mut end = 0;
+    for (range, cluster_width) in clusters {
+        if width + cluster_width > budget {
+            break;
+        }
+        width += cluster_width;
+        end = range.end;
+    }
+    end
+}
+
+/// Start of the longest run of clusters from the end that fits in `budget` columns
+fn suffix_start(clusters: &[(Range<usize>, usize)], budget: usize) -> usize {
+    let mut width = 0;
+    let mut start = clusters.last().map(|(range, _)| range.end).unwrap_or(0);
+    for (range, cluster_width) in clusters.iter().rev() {
+        if width + cluster_width > budget {
+            break;
+        }
+        width += cluster_width;
+        start = range.start;
+    }
+    start
+}
+
+/// Characters of a text within a character range
+fn char_slice(text: &str, range: Range<usize>) -> String {
+    text.chars()
+        .skip(range.start)
+        .take(range.end.saturating_sub(range.start))
+        .collect()
+}
+
+/// Join pieces of a text, moving highlight indices along with the characters they point to.
+/// Indices of characters that were cut are dropped.
+fn assemble(text: &str, pieces: &[Piece], indices: &[usize]) -> Truncated {
+    let chars: Vec<char> = text.chars().collect();
+    let mut truncated = String::new();
+    let mut remapped = Vec::new();
+    let mut position = 0;
+    for piece in pieces {
+        match piece {
+            Piece::Kept(range) => {
+                remapped.extend(
+                    indices
+                        .iter()
+                        .filter(|i| range.contains(i))
+                        .map(|i| i - range.start + position),
+                );
+                truncated.extend(&chars[range.clone()]);
+                position += range.len();
+            }
+            Piece::Inserted(inserted) => {
+                truncated.push_str(inserted);
+                position += inserted.chars().count();
+            }
+        }
+    }
+    Truncated {
+        text: truncated,
+        indices: remapped,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Text mixing wide CJK characters, emoji (including a ZWJ sequence and a flag) and
+    /// characters with combining marks
+    const SAMPLES: &[&str] = &[
+        "日本語のテキスト",
+        "プロジェクト管理ツール",
+        "👨‍👩‍👧 family 🎉 party 🇯🇵",
+        "cafe\u{301} de\u{301}ja\u{300} vu\u{308}",
+        "mi\u{301}x 日本 🎉 e\u{301}t\u{e9}",
+    ];
+
+    type Truncate = fn(&str, &[usize], usize) -> Truncated;
+
+    const TRUNCATIONS: &[(&str, Truncate)] = &[
+        ("truncate_end", truncate_end),
+        ("truncate_start", truncate_start),
+        ("truncate_middle", truncate_middle),
+        ("truncate_path", truncate_path),
+    ];
+
+    fn all_indices(text: &str) -> Vec<usize> {
+        (0..text.chars().count()).collect()
+    }
+
+    /// Check that every grapheme of `truncated` is a whole grapheme of `text`, or part of
+    /// the inserted ellipsis
+    fn assert_graphemes_intact(text: &str, truncated: &str, context: &str) {
+        let original: Vec<&str> = text.graphemes(true).collect();
+        for grapheme in truncated.graphemes(true) {
+            assert!(
+                original.contains(&grapheme) || grapheme == "." || grapheme == "/",
+                "{}: {:?} split a grapheme into {:?}",
+                context,
+                truncated,
+                grapheme
+            );
+        }
+    }
+
+    #[test]
+    fn truncations_fit_width_and_keep_graphemes_whole() {
+        for text in SAMPLES {
+            for max_width in 0..=display_width(text) + 1 {
+                for (name, truncate) in TRUNCATIONS {
+                    let context = format!("{}({:?}, {})", name, text, max_width);
+                    let truncated = truncate(text, &all_indices(text), max_width);
+                    assert!(
+                        display_width(&truncated.text) <= max_width,
+                        "{}: {:?} is wider than {}",
+                        context,
+                        truncated.text,
+                        max_width
+                    );
+                    assert_graphemes_intact(text, &truncated.text, &context);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn highlights_follow_kept_characters_through_ellipsis() {
+        // With every character highlighted, exactly the characters kept from the text stay
+        // highlighted, and none of the inserted ellipsis is
+        for text in SAMPLES {
+            for max_width in 0..=display_width(text) {
+                for (name, truncate) in &TRUNCATIONS[..3] {
+                    let truncated = truncate(text, &all_indices(text), max_width);
+                    let kept: Vec<usize> = truncated
+                        .text
+                        .chars()
+                        .enumerate()
+                        .filter(|(_, c)| *c != '.')
+                        .map(|(i, _)| i)
+                        .collect();
+                    assert_eq!(
+                        truncated.indices, kept,
+                        "{}({:?}, {}) gave {:?}",
+                        name, text, max_width, truncated.text
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn text_that_fits_is_unchanged() {
+        let truncated = truncate_middle("日本語", &[1], 6);
+        assert_eq!(truncated.text, "日本語");
+        assert_eq!(truncated.indices, vec![1]);
+    }
+
+    #[test]
+    fn truncate_end_keeps_whole_wide_characters() {
+        let truncated = truncate_end("日本語のテキスト", &[0, 1, 4], 7);
+        assert_eq!(truncated.text, "日本...");
+        assert_eq!(truncated.indices, vec![0, 1]);
+
+        // A wide character that only half fits is left out rather than split
+        let truncated = truncate_end("a日本語", &[], 5);
+        assert_eq!(truncated.text, "a...");
+    }
+
+    #[test]
+    fn truncate_start_shifts_indices_past_ellipsis() {
+        let truncated = truncate_start("日本語のテキスト", &[0, 6, 7], 7);
+        assert_eq!(truncated.text, "...スト");
+        assert_eq!(truncated.indices, vec![3, 4]);
+    }
+
+    #[test]
+    fn truncate_middle_remaps_both_sides() {
+        let truncated = truncate_middle("abcdefghij", &[0, 5, 9], 7);
+        assert_eq!(truncated.text, "a...hij");
+        assert_eq!(truncated.indices, vec![0, 6]);
+    }
+
+    #[test]
+    fn combining_marks_stay_with_their_base_character() {
+        let text = "e\u{301}e\u{301}e\u{301}e\u{301}e\u{301}";
+        let truncated = truncate_end(text, &[0, 1, 2, 3], 4);
+        assert_eq!(truncated.text, "e\u{301}...");
+        assert_eq!(truncated.indices, vec![0, 1]);
+
+        let truncated = truncate_start(text, &[8, 9], 4);
+        assert_eq!(truncated.text, "...e\u{301}");
+        assert_eq!(truncated.indices, vec![3, 4]);
+    }
+
+    #[test]
+    fn emoji_sequences_are_cut_whole() {
+        let family = "👨‍👩‍👧";
+        let text = format!("{}{}{}", family, family, family);
+        let width = display_width(family);
+        let truncated = truncate_end(&text, &[], width + 3);
+        assert_eq!(truncated.text, format!("{}...", family));
+    }
+
+    #[test]
+    fn truncate_path_replaces_middle_segments() {
+        let truncated = truncate_path("/home/user/projects/日本/src", &[1, 23, 24, 25], 20);
+        assert_eq!(truncated.text, "/home/.../日本/src");
+        assert_eq!(truncated.indices, vec![1, 13, 14, 15]);
+    }
+
+    #[test]
+    fn wrap_splits_wide_words_between_characters() {
+        let lines = wrap("日本語 テキスト", 4);
+        assert_eq!(lines, vec!["日本", "語", "テキ", "スト"]);
+        assert!(lines.iter().all(|line| display_width(line) <= 4));
+    }
+
+    #[test]
+    fn wrap_keeps_graphemes_whole() {
+        for text in SAMPLES {
+            for max_width in 1..=display_width(text) {
+                let lines = wrap(text, max_width);
+                for line in &lines {
+                    assert_graphemes_intact(
+                        text,
+                        line,
+                        &format!("wrap({:?}, {})", text, max_width),
+                    );
+                    // A single cluster wider than the line is the only thing allowed to overflow
+                    assert!(
+                        display_width(line) <= max_width || line.graphemes(true).count() == 1,
+                        "wrap({:?}, {}) gave {:?}",
+                        text,
+                        max_width,
+                        line
+                    );
+                }
+                let rejoined: String = lines.concat().split_whitespace().collect();
+                let original: String = text.split_whitespace().collect();
+                assert_eq!(rejoined, original);
+            }
+        }
+    }
+}
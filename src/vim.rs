@@ -0,0 +1,87 @@
+use zellij_tile::prelude::{BareKey, KeyWithModifier};
+
+/// Mode of the main list when `vim_mode` is enabled
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum VimMode {
+    /// Letters run commands instead of typing into the search
+    #[default]
+    Normal,
+    /// Letters type into the search, Esc goes back to normal mode
+    Insert,
+}
+
+impl VimMode {
+    /// Name shown in the help line
+    pub fn label(&self) -> &'static str {
+        match self {
+            VimMode::Normal => "NORMAL",
+            VimMode::Insert => "INSERT",
+        }
+    }
+}
+
+/// Command typed in normal mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VimCommand {
+    /// `j`
+    Down,
+    /// `k`
+    Up,
+    /// `gg`
+    First,
+    /// `G`
+    Last,
+    /// `/`, switches to insert mode
+    Search,
+    /// `dd`
+    Kill,
+    /// `r`
+    Rename,
+    /// `p`
+    TogglePin,
+}
+
+/// Vim-style modal key handling: the current mode and the first key of a two-key command
+#[derive(Debug, Default)]
+pub struct Vim {
+    mode: VimMode,
+    pending: Option<char>,
+}
+
+impl Vim {
+    pub fn mode(&self) -> VimMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: VimMode) {
+        self.mode = mode;
+        self.pending = None;
+    }
+
+    /// Parse a key typed in normal mode. The first key of `gg` and `dd` is held until the next
+    /// key, which either completes the command or starts over.
+    pub fn command(&mut self, key: &KeyWithModifier) -> Option<VimCommand> {
+        let pending = self.pending.take();
+        let BareKey::Char(c) = key.bare_key else {
+            return None;
+        };
+        if !key.has_no_modifiers() {
+            return None;
+        }
+        match (pending, c) {
+            (Some('g'), 'g') => Some(VimCommand::First),
+            (Some('d'), 'd') => Some(VimCommand::Kill),
+            (_, 'g' | 'd') => {
+                self.pending = Some(c);
+                None
+            }
+            (_, 'j') => Some(VimCommand::Down),
+            (_, 'k') => Some(VimCommand::Up),
+            (_, 'G') => Some(VimCommand::Last),
+            (_, '/') => Some(VimCommand::Search),
+            (_, 'r') => Some(VimCommand::Rename),
+            (_, 'p') => Some(VimCommand::TogglePin),
+            _ => None,
+        }
+    }
+}
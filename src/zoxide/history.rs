@@ -0,0 +1,121 @@
+/// Maximum number of searches kept in the history
+const MAX_ENTRIES: usize = 100;
+
+/// An accepted search: the term and the item that was chosen for it
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    /// Search term that was typed
+    pub term: String,
+    /// Key of the item that was chosen (see `SessionItem::key`)
+    pub item_key: String,
+}
+
+/// History of accepted searches, most recent first
+#[derive(Debug, Default)]
+pub struct SearchHistory {
+    /// Recorded entries, most recent first
+    entries: Vec<HistoryEntry>,
+    /// Index of the entry currently recalled into the search box
+    cursor: Option<usize>,
+}
+
+impl SearchHistory {
+    /// Load entries from store file lines of the form `term<TAB>item_key`
+    pub fn from_lines(lines: &[String]) -> Self {
+        let entries = lines
+            .iter()
+            .filter_map(|line| {
+                let (term, item_key) = line.split_once('\t')?;
+                Some(HistoryEntry {
+                    term: term.to_string(),
+                    item_key: item_key.to_string(),
+                })
+            })
+            .take(MAX_ENTRIES)
+            .collect();
+
+        Self {
+            entries,
+            cursor: None,
+        }
+    }
+
+    /// Serialize entries into store file lines
+    pub fn to_lines(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .map(|entry| format!("{}\t{}", entry.term, entry.item_key))
+            .collect()
+    }
+
+    /// Record an accepted search, moving a repeated search to the front
+    pub fn record(&mut self, term: &str, item_key: String) {
+        // Tabs separate fields in the store file
+        let term = term.trim().replace('\t', " ");
+        if term.is_empty() {
+            return;
+        }
+
+        let entry = HistoryEntry { term, item_key };
+        self.entries.retain(|existing| existing != &entry);
+        self.entries.insert(0, entry);
+        self.entries.truncate(MAX_ENTRIES);
+        self.cursor = None;
+    }
+
+    /// Step back to an older search term, returning it
+    pub fn previous(&mut self) -> Option<String> {
+        let term_count = self.distinct_terms().len();
+        let cursor = match self.cursor {
+            Some(cursor) if cursor + 1 < term_count => cursor + 1,
+            Some(cursor) => cursor,
+            None if term_count > 0 => 0,
+            None => return None,
+        };
+        self.cursor = Some(cursor);
+        self.distinct_terms()
+            .get(cursor)
+            .map(|term| term.to_string())
+    }
+
+    /// Step forward to a newer search term, returning it, or `None` once past the newest
+    pub fn next(&mut self) -> Option<String> {
+        match self.cursor {
+            Some(0) | None => {
+                self.cursor = None;
+                None
+            }
+            Some(cursor) => {
+                self.cursor = Some(cursor - 1);
+                self.distinct_terms()
+                    .get(cursor - 1)
+                    .map(|term| term.to_string())
+            }
+        }
+    }
+
+    /// Stop recalling, e.g. once the user edits the search term
+    pub fn reset_cursor(&mut self) {
+        self.cursor = None;
+    }
+
+    /// Keys of the items previously chosen for a term, most recent first
+    pub fn chosen_for<'a>(&'a self, term: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        let term = term.trim();
+        self.entries
+            .iter()
+            .filter(move |entry| entry.term == term)
+            .map(|entry| entry.item_key.as_str())
+    }
+
+    /// Unique search terms, most recent first
+    fn distinct_terms(&self) -> Vec<&str> {
+        let mut terms: Vec<&str> = Vec::new();
+        for entry in &self.entries {
+            if !terms.contains(&entry.term.as_str()) {
+                terms.push(&entry.term);
+            }
+        }
+        terms
+    }
+}
@@ -0,0 +1,219 @@
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use regex::{Regex, RegexBuilder};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Algorithm used to match a search term against item text
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SearchMode {
+    /// Skim-style fuzzy matching
+    #[default]
+    Fuzzy,
+    /// Case-insensitive substring matching
+    Exact,
+    /// Case-insensitive prefix matching on the basename
+    Prefix,
+    /// Regular expression matching
+    Regex,
+}
+
+impl SearchMode {
+    /// Parse a mode from its configuration name
+    pub fn from_config(value: &str) -> Option<Self> {
+        match value.trim() {
+            "fuzzy" => Some(SearchMode::Fuzzy),
+            "exact" => Some(SearchMode::Exact),
+            "prefix" => Some(SearchMode::Prefix),
+            "regex" => Some(SearchMode::Regex),
+            _ => None,
+        }
+    }
+
+    /// Name shown in the UI
+    pub fn label(&self) -> &'static str {
+        match self {
+            SearchMode::Fuzzy => "fuzzy",
+            SearchMode::Exact => "exact",
+            SearchMode::Prefix => "prefix",
+            SearchMode::Regex => "regex",
+        }
+    }
+
+    /// The mode that follows this one when toggling at runtime
+    pub fn next(&self) -> Self {
+        match self {
+            SearchMode::Fuzzy => SearchMode::Exact,
+            SearchMode::Exact => SearchMode::Prefix,
+            SearchMode::Prefix => SearchMode::Regex,
+            SearchMode::Regex => SearchMode::Fuzzy,
+        }
+    }
+}
+
+/// How search results are ordered
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SortPolicy {
+    /// Match score only
+    Score,
+    /// Sessions before directories, then by match score
+    #[default]
+    SessionsFirst,
+    /// Match score weighted by the zoxide ranking of the item's directory
+    Frecency,
+}
+
+impl SortPolicy {
+    /// Parse a policy from its configuration name
+    pub fn from_config(value: &str) -> Option<Self> {
+        match value.trim() {
+            "score" => Some(SortPolicy::Score),
+            "sessions_first" => Some(SortPolicy::SessionsFirst),
+            "frecency" => Some(SortPolicy::Frecency),
+            _ => None,
+        }
+    }
+}
+
+/// Matches search terms against text using the selected search mode
+pub struct Matcher {
+    /// Active search mode
+    mode: SearchMode,
+    /// Fuzzy matcher instance
+    fuzzy: SkimMatcherV2,
+    /// Compiled regexes by pattern, `None` for patterns that failed to compile
+    regex_cache: RefCell<HashMap<String, Option<Regex>>>,
+}
+
+impl Default for Matcher {
+    fn default() -> Self {
+        Self {
+            mode: SearchMode::default(),
+            fuzzy: SkimMatcherV2::default().use_cache(true),
+            regex_cache: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl Matcher {
+    /// Get active search mode
+    pub fn mode(&self) -> SearchMode {
+        self.mode
+    }
+
+    /// Set active search mode
+    pub fn set_mode(&mut self, mode: SearchMode) {
+        self.mode = mode;
+    }
+
+    /// Drop compiled regexes from previous searches
+    pub fn clear_cache(&self) {
+        self.regex_cache.borrow_mut().clear();
+    }
+
+    /// Match a pattern against text, returning a score (higher is better) and the
+    /// matched character indices. In prefix mode the caller passes the basename.
+    pub fn match_text(&self, text: &str, pattern: &str) -> Option<(i64, Vec<usize>)> {
+        match self.mode {
+            SearchMode::Fuzzy => self.fuzzy.fuzzy_indices(text, pattern),
+            SearchMode::Exact => Self::match_substring(text, pattern, false),
+            SearchMode::Prefix => Self::match_substring(text, pattern, true),
+            SearchMode::Regex => self.match_regex(text, pattern),
+        }
+    }
+
+    /// Case-insensitive substring match, earlier and tighter matches scoring higher
+    fn match_substring(text: &str, pattern: &str, prefix_only: bool) -> Option<(i64, Vec<usize>)> {
+        // Lowercasing can turn one character into several, e.g. `İ`, so remember the index of
+        // the original character each lowercase one comes from
+        let (text_chars, original_indices): (Vec<char>, Vec<usize>) = text
+            .chars()
+            .enumerate()
+            .flat_map(|(i, c)| c.to_lowercase().map(move |lower| (lower, i)))
+            .unzip();
+        let pattern_chars: Vec<char> = pattern.chars().flat_map(char::to_lowercase).collect();
+        if pattern_chars.is_empty() {
+            return None;
+        }
+
+        let found = if prefix_only {
+            text_chars.starts_with(&pattern_chars).then_some(0)?
+        } else {
+            text_chars
+                .windows(pattern_chars.len())
+                .position(|window| window == pattern_chars.as_slice())?
+        };
+
+        let start = original_indices[found];
+        let end = original_indices[found + pattern_chars.len() - 1] + 1;
+        let text_len = text.chars().count();
+        let score = 1000 - (start as i64) - (text_len - (end - start)) as i64;
+        Some((score, (start..end).collect()))
+    }
+
+    /// Regex match, case-insensitive unless the pattern contains uppercase letters
+    fn match_regex(&self, text: &str, pattern: &str) -> Option<(i64, Vec<usize>)> {
+        let mut cache = self.regex_cache.borrow_mut();
+        let regex = cache
+            .entry(pattern.to_string())
+            .or_insert_with(|| {
+                RegexBuilder::new(pattern)
+                    .case_insensitive(!pattern.chars().any(char::is_uppercase))
+                    .build()
+                    .ok()
+            })
+            .as_ref()?;
+
+        let found = regex.find(text)?;
+        let start = text[..found.start()].chars().count();
+        let len = found.as_str().chars().count();
+        let score = 1000 - (start as i64) - (len as i64);
+        Some((score, (start..start + len).collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matcher(mode: SearchMode) -> Matcher {
+        let mut matcher = Matcher::default();
+        matcher.set_mode(mode);
+        matcher
+    }
+
+    #[test]
+    fn substring_match_indices_follow_characters_that_lowercase_to_several() {
+        // `İ` lowercases to `i` and a combining dot, two characters
+        let exact = matcher(SearchMode::Exact);
+        assert_eq!(
+            exact
+                .match_text("/home/İstanbul/api", "api")
+                .map(|(_, i)| i),
+            Some(vec![15, 16, 17])
+        );
+        assert_eq!(
+            exact
+                .match_text("/home/İstanbul/api", "stan")
+                .map(|(_, i)| i),
+            Some(vec![7, 8, 9, 10])
+        );
+        let prefix = matcher(SearchMode::Prefix);
+        assert_eq!(
+            prefix.match_text("İstanbul", "İst").map(|(_, i)| i),
+            Some(vec![0, 1, 2])
+        );
+    }
+
+    #[test]
+    fn substring_match_is_case_insensitive() {
+        let exact = matcher(SearchMode::Exact);
+        assert_eq!(
+            exact
+                .match_text("/home/user/MyApi", "myapi")
+                .map(|(_, i)| i),
+            Some((11..16).collect())
+        );
+        assert_eq!(exact.match_text("/home/user/web", "api"), None);
+    }
+}
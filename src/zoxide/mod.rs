@@ -1,5 +1,9 @@
 pub mod directory;
+pub mod history;
+pub mod matcher;
+pub mod query;
 pub mod search;
 
 pub use directory::ZoxideDirectory;
+pub use matcher::{SearchMode, SortPolicy};
 pub use search::SearchEngine;
@@ -0,0 +1,110 @@
+use crate::session::SessionItem;
+
+/// Kind of item a query can be restricted to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ItemKind {
+    /// Running sessions (`s:`)
+    Session,
+    /// Zoxide directories (`d:`)
+    Directory,
+    /// Resurrectable sessions (`r:`)
+    Resurrectable,
+}
+
+impl ItemKind {
+    /// Parse a kind prefix such as `s:`, returning the kind and the rest of the token
+    fn strip_prefix(token: &str) -> Option<(Self, &str)> {
+        let (kind, rest) = token.split_at_checked(2)?;
+        match kind {
+            "s:" => Some((ItemKind::Session, rest)),
+            "d:" => Some((ItemKind::Directory, rest)),
+            "r:" => Some((ItemKind::Resurrectable, rest)),
+            _ => None,
+        }
+    }
+
+    /// Check if an item is of this kind
+    pub fn matches(&self, item: &SessionItem) -> bool {
+        match self {
+            ItemKind::Session => matches!(item, SessionItem::ExistingSession { .. }),
+            ItemKind::Directory => matches!(item, SessionItem::Directory { .. }),
+            ItemKind::Resurrectable => matches!(item, SessionItem::ResurrectableSession { .. }),
+        }
+    }
+}
+
+/// Part of an item a term is matched against
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchField {
+    /// The whole display text
+    All,
+    /// Only the session name (`@`)
+    Name,
+    /// Only the path (`/`)
+    Path,
+}
+
+/// A single space-separated search term
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryTerm {
+    /// Text to match
+    pub text: String,
+    /// Part of the item to match against
+    pub field: SearchField,
+    /// Whether items matching this term are excluded (`!`)
+    pub negated: bool,
+}
+
+/// A parsed search query.
+///
+/// Syntax, with space-separated terms that must all match:
+/// - `s:`, `d:`, `r:` restrict results to sessions, directories or resurrectable sessions
+/// - `@term` matches only the session name, `/term` matches only the path
+/// - `!term` excludes items containing the term
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Query {
+    /// Kind of item to restrict results to
+    pub kind: Option<ItemKind>,
+    /// Terms that must all match
+    pub terms: Vec<QueryTerm>,
+}
+
+impl Query {
+    /// Parse a search term into a query
+    pub fn parse(input: &str) -> Self {
+        let mut query = Query::default();
+
+        for token in input.split_whitespace() {
+            let token = match ItemKind::strip_prefix(token) {
+                Some((kind, rest)) => {
+                    query.kind = Some(kind);
+                    rest
+                }
+                None => token,
+            };
+
+            let (negated, token) = match token.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, token),
+            };
+
+            let (field, text) = if let Some(rest) = token.strip_prefix('@') {
+                (SearchField::Name, rest)
+            } else if let Some(rest) = token.strip_prefix('/') {
+                (SearchField::Path, rest)
+            } else {
+                (SearchField::All, token)
+            };
+
+            if !text.is_empty() {
+                query.terms.push(QueryTerm {
+                    text: text.to_string(),
+                    field,
+                    negated,
+                });
+            }
+        }
+
+        query
+    }
+}
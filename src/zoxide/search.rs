@@ -1,6 +1,19 @@
 use crate::session::SessionItem;
-use fuzzy_matcher::skim::SkimMatcherV2;
-use fuzzy_matcher::FuzzyMatcher;
+use crate::text_input::{InputEvent, TextInput};
+use crate::zoxide::history::SearchHistory;
+use crate::zoxide::matcher::{Matcher, SearchMode, SortPolicy};
+use crate::zoxide::query::{Query, QueryTerm, SearchField};
+use std::collections::HashMap;
+use std::ops::Range;
+use zellij_tile::prelude::KeyWithModifier;
+
+/// Score multiplier for directory matches within the final path segment
+const BASENAME_WEIGHT: i64 = 4;
+/// Score multiplier for directory matches within the generated session name
+const SESSION_NAME_WEIGHT: i64 = 3;
+/// Bonus per matched character when the term matches contiguously from the start of a
+/// path segment or word, e.g. `api` in `/work/api-server`
+const SEGMENT_BOUNDARY_BONUS: i64 = 20;
 
 /// Search result containing an item and match information
 #[derive(Debug, Clone)]
@@ -14,11 +27,20 @@ pub struct SearchResult {
 }
 
 /// Handles fuzzy searching across sessions and directories
+#[derive(Default)]
 pub struct SearchEngine {
     /// Current search term
-    search_term: String,
-    /// Fuzzy matcher instance
-    matcher: SkimMatcherV2,
+    input: TextInput,
+    /// Matcher for the active search mode
+    matcher: Matcher,
+    /// How results are ordered
+    sort_policy: SortPolicy,
+    /// Zoxide ranking of each directory, used for frecency ordering
+    rankings: HashMap<String, f64>,
+    /// Previously accepted searches
+    history: SearchHistory,
+    /// Whether items previously chosen for the search term are ranked first
+    rank_by_history: bool,
     /// Current search results
     results: Vec<SearchResult>,
     /// Selected result index
@@ -27,23 +49,14 @@ pub struct SearchEngine {
     is_searching: bool,
 }
 
-impl Default for SearchEngine {
-    fn default() -> Self {
-        Self {
-            search_term: String::new(),
-            matcher: SkimMatcherV2::default().use_cache(true),
-            results: Vec::new(),
-            selected_index: None,
-            is_searching: false,
-        }
-    }
-}
-
 impl SearchEngine {
     /// Update search term and perform search
     pub fn update_search(&mut self, term: String, items: &[SessionItem]) {
-        self.search_term = term;
-        self.is_searching = !self.search_term.is_empty();
+        // Only replace the text when it changed, so re-running a search keeps the cursor
+        if term != self.input.text() {
+            self.input.set_text(&term);
+        }
+        self.is_searching = !self.input.is_empty();
 
         if self.is_searching {
             self.perform_search(items);
@@ -53,21 +66,87 @@ impl SearchEngine {
         }
     }
 
-    /// Add character to search term
-    pub fn add_char(&mut self, c: char, items: &[SessionItem]) {
-        self.search_term.push(c);
-        self.update_search(self.search_term.clone(), items);
+    /// Get active search mode
+    pub fn search_mode(&self) -> SearchMode {
+        self.matcher.mode()
+    }
+
+    /// Set search mode and re-run the current search
+    pub fn set_search_mode(&mut self, mode: SearchMode, items: &[SessionItem]) {
+        self.matcher.set_mode(mode);
+        self.update_search(self.input.text().to_string(), items);
+    }
+
+    /// Set how results are ordered
+    pub fn set_sort_policy(&mut self, sort_policy: SortPolicy) {
+        self.sort_policy = sort_policy;
+    }
+
+    /// Set the zoxide ranking of each directory path
+    pub fn set_rankings(&mut self, rankings: HashMap<String, f64>) {
+        self.rankings = rankings;
+    }
+
+    /// Set whether items previously chosen for the search term are ranked first
+    pub fn set_rank_by_history(&mut self, rank_by_history: bool) {
+        self.rank_by_history = rank_by_history;
+    }
+
+    /// Get search history
+    pub fn history(&self) -> &SearchHistory {
+        &self.history
+    }
+
+    /// Replace the search history, e.g. once it's loaded from disk
+    pub fn set_history(&mut self, history: SearchHistory) {
+        self.history = history;
+    }
+
+    /// Record the selected item as the accepted result of the current search
+    pub fn record_selection(&mut self, item: &SessionItem) {
+        if self.is_searching {
+            self.history.record(self.input.text(), item.key());
+        }
+    }
+
+    /// Recall the previous search term from history, returning whether one was recalled
+    pub fn recall_previous(&mut self, items: &[SessionItem]) -> bool {
+        match self.history.previous() {
+            Some(term) => {
+                self.update_search(term, items);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Recall the next search term from history, clearing the search past the newest one
+    pub fn recall_next(&mut self, items: &[SessionItem]) {
+        let term = self.history.next().unwrap_or_default();
+        self.update_search(term, items);
+    }
+
+    /// Handle a line-editing key in the search box, re-running the search if the term changed
+    pub fn handle_key(&mut self, key: &KeyWithModifier, items: &[SessionItem]) -> InputEvent {
+        let event = self.input.handle_key(key);
+        if event == InputEvent::Changed {
+            self.history.reset_cursor();
+            self.update_search(self.input.text().to_string(), items);
+        }
+        event
     }
 
-    /// Remove last character from search term
-    pub fn backspace(&mut self, items: &[SessionItem]) {
-        self.search_term.pop();
-        self.update_search(self.search_term.clone(), items);
+    /// Insert pasted text at the cursor
+    pub fn paste(&mut self, text: &str, items: &[SessionItem]) {
+        self.history.reset_cursor();
+        self.input.insert_str(text);
+        self.update_search(self.input.text().to_string(), items);
     }
 
     /// Clear search term
     pub fn clear(&mut self) {
-        self.search_term.clear();
+        self.history.reset_cursor();
+        self.input.clear();
         self.results.clear();
         self.selected_index = None;
         self.is_searching = false;
@@ -75,7 +154,12 @@ impl SearchEngine {
 
     /// Get current search term
     pub fn search_term(&self) -> &str {
-        &self.search_term
+        self.input.text()
+    }
+
+    /// Get search input (for UI rendering)
+    pub fn input(&self) -> &TextInput {
+        &self.input
     }
 
     /// Check if currently searching
@@ -119,6 +203,13 @@ impl SearchEngine {
         }
     }
 
+    /// Select a result by index
+    pub fn select(&mut self, index: usize) {
+        if index < self.results.len() {
+            self.selected_index = Some(index);
+        }
+    }
+
     /// Get currently selected item
     pub fn selected_item(&self) -> Option<&SessionItem> {
         self.selected_index
@@ -128,16 +219,16 @@ impl SearchEngine {
 
     /// Perform fuzzy search on items
     fn perform_search(&mut self, items: &[SessionItem]) {
+        let query = Query::parse(self.input.text());
         let mut matches = Vec::new();
+        self.matcher.clear_cache();
 
         for item in items {
-            // Create the display text that will actually be shown
-            let display_text = Self::get_display_text_for_search(item);
+            if query.kind.is_some_and(|kind| !kind.matches(item)) {
+                continue;
+            }
 
-            // Match against the actual display text
-            if let Some((score, indices)) =
-                self.matcher.fuzzy_indices(&display_text, &self.search_term)
-            {
+            if let Some((score, indices)) = self.match_item(item, &query.terms) {
                 matches.push(SearchResult {
                     item: item.clone(),
                     score,
@@ -146,17 +237,43 @@ impl SearchEngine {
             }
         }
 
-        // Sort results: sessions first, then by score
-        matches.sort_by(|a, b| {
-            let a_is_session = a.item.is_session() || a.item.is_resurrectable_session();
-            let b_is_session = b.item.is_session() || b.item.is_resurrectable_session();
+        match self.sort_policy {
+            SortPolicy::Score => {
+                matches.sort_by_key(|result| std::cmp::Reverse(result.score));
+            }
+            SortPolicy::SessionsFirst => {
+                // Sort results: sessions first, then by score
+                matches.sort_by(|a, b| {
+                    let a_is_session = a.item.is_session() || a.item.is_resurrectable_session();
+                    let b_is_session = b.item.is_session() || b.item.is_resurrectable_session();
 
-            match (a_is_session, b_is_session) {
-                (true, false) => std::cmp::Ordering::Less, // a (session) comes first
-                (false, true) => std::cmp::Ordering::Greater, // b (session) comes first
-                _ => b.score.cmp(&a.score),                // Same type, sort by score
+                    match (a_is_session, b_is_session) {
+                        (true, false) => std::cmp::Ordering::Less, // a (session) comes first
+                        (false, true) => std::cmp::Ordering::Greater, // b (session) comes first
+                        _ => b.score.cmp(&a.score),                // Same type, sort by score
+                    }
+                });
+            }
+            SortPolicy::Frecency => {
+                matches.sort_by(|a, b| {
+                    self.frecency_score(b)
+                        .partial_cmp(&self.frecency_score(a))
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
             }
-        });
+        }
+
+        if self.rank_by_history {
+            // Items chosen for this exact term before go first, most recently chosen first
+            let chosen: Vec<&str> = self.history.chosen_for(self.input.text()).collect();
+            matches.sort_by_key(|result| {
+                let key = result.item.key();
+                chosen
+                    .iter()
+                    .position(|chosen_key| *chosen_key == key)
+                    .unwrap_or(chosen.len())
+            });
+        }
 
         self.results = matches;
 
@@ -176,6 +293,212 @@ impl SearchEngine {
         }
     }
 
+    /// Match every query term against an item, returning the combined score and the
+    /// matched character indices in the display text
+    fn match_item(&self, item: &SessionItem, terms: &[QueryTerm]) -> Option<(i64, Vec<usize>)> {
+        // Create the display text that will actually be shown
+        let display_text = Self::get_display_text_for_search(item);
+        let mut total_score = 0;
+        let mut all_indices = Vec::new();
+
+        let prefix_mode = self.matcher.mode() == SearchMode::Prefix;
+
+        for term in terms {
+            // Match against the searched field, remembering where it sits in the display text
+            // so indices can be shifted back onto the display text for highlighting.
+            // Prefix mode only looks at the basename: the session name or last path segment
+            let (field_text, offset) = match term.field {
+                SearchField::All if prefix_mode => {
+                    let span = Self::name_span(item).or_else(|| {
+                        Self::path_span(item).map(|span| Self::basename_span(item, span))
+                    });
+                    Self::get_field(&display_text, span)?
+                }
+                SearchField::All => (display_text.clone(), Some(0)),
+                SearchField::Name => Self::get_field(&display_text, Self::name_span(item))
+                    .or_else(|| Self::directory_session_name(item).map(|name| (name, None)))?,
+                SearchField::Path if prefix_mode => {
+                    let span = Self::path_span(item).map(|span| Self::basename_span(item, span));
+                    Self::get_field(&display_text, span)?
+                }
+                SearchField::Path => Self::get_field(&display_text, Self::path_span(item))?,
+            };
+
+            if term.negated {
+                if field_text
+                    .to_lowercase()
+                    .contains(&term.text.to_lowercase())
+                {
+                    return None;
+                }
+                continue;
+            }
+
+            let (score, indices) = match item {
+                SessionItem::Directory { session_name, .. }
+                    if !prefix_mode && term.field != SearchField::Name =>
+                {
+                    self.match_path(&field_text, session_name, &term.text)?
+                }
+                _ => self.matcher.match_text(&field_text, &term.text)?,
+            };
+            total_score += score;
+            if let Some(offset) = offset {
+                all_indices.extend(indices.into_iter().map(|i| i + offset));
+            }
+        }
+
+        all_indices.sort_unstable();
+        all_indices.dedup();
+        Some((total_score, all_indices))
+    }
+
+    /// Match a term against a directory path. Matches in the final segment or the generated
+    /// session name score much higher than matches scattered through parent segments, so
+    /// `api` ranks `~/work/api` above `~/applications/pi`.
+    fn match_path(
+        &self,
+        path: &str,
+        session_name: &str,
+        pattern: &str,
+    ) -> Option<(i64, Vec<usize>)> {
+        let basename_start = Self::basename_start(path);
+        let basename: String = path.chars().skip(basename_start).collect();
+
+        let path_match = self.matcher.match_text(path, pattern);
+        let (mut score, mut indices) =
+            if let Some((score, indices)) = self.matcher.match_text(&basename, pattern) {
+                let indices = indices.into_iter().map(|i| i + basename_start).collect();
+                (score * BASENAME_WEIGHT, indices)
+            } else if let Some((score, _)) = self.matcher.match_text(session_name, pattern) {
+                // The session name isn't displayed, so highlight the path match if there is one
+                let indices = path_match.map(|(_, indices)| indices).unwrap_or_default();
+                (score * SESSION_NAME_WEIGHT, indices)
+            } else {
+                path_match?
+            };
+
+        if let Some(boundary_indices) = Self::segment_boundary_match(path, pattern) {
+            score += SEGMENT_BOUNDARY_BONUS * boundary_indices.len() as i64;
+            // Highlight the contiguous run rather than scattered fuzzy indices
+            indices = boundary_indices;
+        }
+
+        Some((score, indices))
+    }
+
+    /// Character index where the final segment of a path starts
+    fn basename_start(path: &str) -> usize {
+        let trimmed = path.trim_end_matches('/');
+        trimmed
+            .rfind('/')
+            .map(|i| trimmed[..=i].chars().count())
+            .unwrap_or(0)
+    }
+
+    /// Find the term as a contiguous, case-insensitive run starting at a path segment or
+    /// word boundary, preferring the occurrence closest to the end of the path
+    fn segment_boundary_match(path: &str, pattern: &str) -> Option<Vec<usize>> {
+        let path_chars: Vec<char> = path.chars().flat_map(char::to_lowercase).collect();
+        let pattern_chars: Vec<char> = pattern.chars().flat_map(char::to_lowercase).collect();
+        // Lowercasing can change the character count; skip those paths to keep indices aligned
+        if path_chars.len() != path.chars().count() || pattern_chars.is_empty() {
+            return None;
+        }
+
+        let is_boundary = |start: usize| {
+            start == 0 || matches!(path_chars[start - 1], '/' | '-' | '_' | '.' | ' ')
+        };
+        let start = path_chars
+            .windows(pattern_chars.len())
+            .enumerate()
+            .rev()
+            .find(|(start, window)| *window == pattern_chars.as_slice() && is_boundary(*start))
+            .map(|(start, _)| start)?;
+
+        Some((start..start + pattern_chars.len()).collect())
+    }
+
+    /// Match score weighted by the zoxide ranking of the result's directory
+    fn frecency_score(&self, result: &SearchResult) -> f64 {
+        let directory = match &result.item {
+            SessionItem::ExistingSession { directory, .. } => Some(directory),
+            SessionItem::Directory { path, .. } => Some(path),
+            SessionItem::ResurrectableSession { .. } => None,
+        };
+        let ranking = directory
+            .and_then(|directory| self.rankings.get(directory))
+            .copied()
+            .unwrap_or(0.0);
+        result.score as f64 * (1.0 + ranking.ln_1p())
+    }
+
+    /// Extract a field from the display text by its character span
+    fn get_field(
+        display_text: &str,
+        span: Option<Range<usize>>,
+    ) -> Option<(String, Option<usize>)> {
+        let span = span?;
+        let text = display_text
+            .chars()
+            .skip(span.start)
+            .take(span.len())
+            .collect();
+        Some((text, Some(span.start)))
+    }
+
+    /// Character span of the session name within the display text
+    fn name_span(item: &SessionItem) -> Option<Range<usize>> {
+        match item {
+            SessionItem::ExistingSession { name, .. }
+            | SessionItem::ResurrectableSession { name, .. } => {
+                // Both are prefixed with an icon and a space
+                Some(2..2 + name.chars().count())
+            }
+            SessionItem::Directory { .. } => None,
+        }
+    }
+
+    /// Character span of the path within the display text
+    fn path_span(item: &SessionItem) -> Option<Range<usize>> {
+        match item {
+            SessionItem::ExistingSession {
+                name, directory, ..
+            } => {
+                // "● name (directory)"
+                let start = 2 + name.chars().count() + 2;
+                Some(start..start + directory.chars().count())
+            }
+            SessionItem::ResurrectableSession { .. } => None,
+            SessionItem::Directory { path, .. } => Some(0..path.chars().count()),
+        }
+    }
+
+    /// Narrow a path span down to its last segment
+    fn basename_span(item: &SessionItem, path_span: Range<usize>) -> Range<usize> {
+        let path = match item {
+            SessionItem::ExistingSession { directory, .. } => directory,
+            SessionItem::Directory { path, .. } => path,
+            SessionItem::ResurrectableSession { .. } => return path_span,
+        };
+        let trimmed = path.trim_end_matches('/');
+        let basename_len = trimmed
+            .rsplit('/')
+            .next()
+            .map(|basename| basename.chars().count())
+            .unwrap_or(0);
+        let end = path_span.start + trimmed.chars().count();
+        end - basename_len..end
+    }
+
+    /// Generated session name of a directory, which isn't part of its display text
+    fn directory_session_name(item: &SessionItem) -> Option<String> {
+        match item {
+            SessionItem::Directory { session_name, .. } => Some(session_name.clone()),
+            _ => None,
+        }
+    }
+
     /// Get the display text used for searching (matches what's rendered)
     fn get_display_text_for_search(item: &SessionItem) -> String {
         match item {
@@ -187,7 +510,7 @@ impl SearchEngine {
                 let prefix = if *is_current { "● " } else { "○ " };
                 format!("{}{} ({})", prefix, name, directory)
             }
-            SessionItem::ResurrectableSession { name, duration } => {
+            SessionItem::ResurrectableSession { name, duration, .. } => {
                 // For resurrectable sessions, we show the name and duration
                 format!(
                     "↺ {} (created {} ago)",
@@ -202,3 +525,113 @@ impl SearchEngine {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Directory item named after its final path segment
+    fn directory(path: &str) -> SessionItem {
+        let session_name = path.rsplit('/').next().unwrap_or_default();
+        directory_with_name(path, session_name)
+    }
+
+    fn directory_with_name(path: &str, session_name: &str) -> SessionItem {
+        SessionItem::Directory {
+            path: path.to_string(),
+            session_name: session_name.to_string(),
+        }
+    }
+
+    /// Paths of the fixture directories matching `term`, best match first
+    fn ranked_paths(items: &[SessionItem], term: &str) -> Vec<String> {
+        let mut engine = SearchEngine::default();
+        engine.set_sort_policy(SortPolicy::Score);
+        engine.update_search(term.to_string(), items);
+        engine
+            .results()
+            .iter()
+            .filter_map(|result| match &result.item {
+                SessionItem::Directory { path, .. } => Some(path.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn fixture() -> Vec<SessionItem> {
+        vec![
+            directory("/home/user/api/docs"),
+            directory("/home/user/applications/pi"),
+            directory("/home/user/work/api"),
+            directory("/home/user/work/a_pi"),
+            directory("/home/user/core/notes/app"),
+            directory("/home/user/src/rapid"),
+            directory("/home/user/src/my-api"),
+            directory_with_name("/home/user/src/x", "core"),
+        ]
+    }
+
+    fn position(ranked: &[String], path: &str) -> usize {
+        ranked
+            .iter()
+            .position(|ranked_path| ranked_path == path)
+            .unwrap_or_else(|| panic!("{} did not match: {:?}", path, ranked))
+    }
+
+    #[test]
+    fn basename_match_ranks_above_match_spread_over_parent_segments() {
+        let ranked = ranked_paths(&fixture(), "api");
+        assert!(
+            position(&ranked, "/home/user/work/api")
+                < position(&ranked, "/home/user/applications/pi"),
+            "{:?}",
+            ranked
+        );
+    }
+
+    #[test]
+    fn fuzzy_basename_match_ranks_above_exact_match_in_parent_segment() {
+        let ranked = ranked_paths(&fixture(), "api");
+        assert!(
+            position(&ranked, "/home/user/work/a_pi") < position(&ranked, "/home/user/api/docs"),
+            "{:?}",
+            ranked
+        );
+    }
+
+    #[test]
+    fn session_name_match_ranks_above_match_deep_in_path() {
+        let ranked = ranked_paths(&fixture(), "core");
+        assert!(
+            position(&ranked, "/home/user/src/x") < position(&ranked, "/home/user/core/notes/app"),
+            "{:?}",
+            ranked
+        );
+    }
+
+    #[test]
+    fn segment_boundary_match_ranks_above_match_inside_word() {
+        let ranked = ranked_paths(&fixture(), "api");
+        assert!(
+            position(&ranked, "/home/user/src/my-api") < position(&ranked, "/home/user/src/rapid"),
+            "{:?}",
+            ranked
+        );
+    }
+
+    #[test]
+    fn segment_boundary_match_needs_start_of_segment_or_word() {
+        assert!(SearchEngine::segment_boundary_match("/home/user/src/rapid", "api").is_none());
+        assert!(SearchEngine::segment_boundary_match("/home/user/src/my-api", "api").is_some());
+        assert!(SearchEngine::segment_boundary_match("/home/user/src/my_API", "api").is_some());
+    }
+
+    #[test]
+    fn segment_boundary_match_highlights_contiguous_run() {
+        let engine = SearchEngine::default();
+        let (_, indices) = engine
+            .match_path("/home/user/src/my-api", "my-api", "api")
+            .unwrap();
+        assert_eq!(indices, vec![18, 19, 20]);
+    }
+}